@@ -0,0 +1,10 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_05::Day05;
+
+fn solution(c: &mut Criterion) {
+    bench_solution::<Day05>(c, include_str!("../part1.txt"));
+}
+
+criterion_group!(benches, solution);
+criterion_main!(benches);
@@ -0,0 +1,253 @@
+//! Finding which maps take one category to another, for almanacs whose maps
+//! do not simply run from seeds to locations.
+
+use std::fmt::Display;
+
+use crate::convert_maps_to_transform_layers;
+use crate::intervals::{Interval, IntervalSet};
+use crate::parser::CategoryMap;
+use crate::transform::*;
+
+/// The categories of an almanac, with a map as the edge from each map's
+/// source to its destination. Building one checks the maps never loop.
+#[derive(Debug)]
+pub struct CategoryGraph<'a> {
+    maps: &'a [CategoryMap],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// No maps lead from the first category to the second.
+    NoPath(String, String),
+    /// More than one way leads from the first category to the second.
+    AmbiguousPath(String, String),
+    /// Following the maps through these categories comes back to the first.
+    Cycle(Vec<String>),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::NoPath(from, to) => write!(f, "no maps lead from `{}` to `{}`", from, to),
+            GraphError::AmbiguousPath(from, to) => write!(f, "more than one way leads from `{}` to `{}`", from, to),
+            GraphError::Cycle(categories) => write!(f, "the maps loop through `{}`", categories.join("` -> `")),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+impl<'a> CategoryGraph<'a> {
+    pub fn new(maps: &'a [CategoryMap]) -> Result<CategoryGraph<'a>, GraphError> {
+        let graph = CategoryGraph { maps };
+
+        let mut finished = Vec::<&str>::new();
+        for map in maps {
+            graph.check_for_cycle(&map.source, &mut vec![], &mut finished)?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Depth-first search from `category`, where `path` is how it was reached
+    /// and `finished` are categories already known not to lead into a cycle.
+    fn check_for_cycle(
+        self: &Self,
+        category: &'a str,
+        path: &mut Vec<&'a str>,
+        finished: &mut Vec<&'a str>)
+        -> Result<(), GraphError>
+    {
+        if finished.contains(&category) {
+            return Ok(());
+        }
+
+        if let Some(start) = path.iter().position(|previous| *previous == category) {
+            let mut cycle = path[start..].iter().map(|c| c.to_string()).collect::<Vec<String>>();
+            cycle.push(category.to_string());
+            return Err(GraphError::Cycle(cycle));
+        }
+
+        path.push(category);
+        for map in self.maps_from(category) {
+            self.check_for_cycle(&map.destination, path, finished)?;
+        }
+        path.pop();
+
+        finished.push(category);
+        Ok(())
+    }
+
+    fn maps_from(self: &Self, category: &'a str) -> impl Iterator<Item = &'a CategoryMap> + 'a {
+        self.maps.iter().filter(move |map| map.source == category)
+    }
+
+    /// The maps to apply, in order, to take `from` to `to`. There has to be
+    /// exactly one way to get there, so the answer never depends on which is picked.
+    pub fn path(self: &Self, from: &str, to: &str) -> Result<Vec<&'a CategoryMap>, GraphError> {
+        let mut paths = vec![];
+        self.find_paths(from, to, &mut vec![], &mut paths);
+
+        match paths.len() {
+            0 => Err(GraphError::NoPath(from.to_string(), to.to_string())),
+            1 => Ok(paths.pop().unwrap()),
+            _ => Err(GraphError::AmbiguousPath(from.to_string(), to.to_string())),
+        }
+    }
+
+    /// Collects the paths from `category` to `to`, stopping once there are two.
+    fn find_paths(
+        self: &Self,
+        category: &str,
+        to: &str,
+        path: &mut Vec<&'a CategoryMap>,
+        paths: &mut Vec<Vec<&'a CategoryMap>>)
+    {
+        if category == to {
+            paths.push(path.clone());
+            return;
+        }
+
+        for map in self.maps.iter().filter(|map| map.source == category) {
+            if paths.len() == 2 {
+                return;
+            }
+
+            path.push(map);
+            self.find_paths(&map.destination, to, path, paths);
+            path.pop();
+        }
+    }
+
+    /// The transform layers of [`CategoryGraph::path`].
+    pub fn transform_layers(self: &Self, from: &str, to: &str) -> Result<Vec<Vec<IntervalTransform>>, GraphError> {
+        let maps = self.path(from, to)?
+            .into_iter()
+            .map(|map| &map.entries)
+            .collect();
+
+        Ok(convert_maps_to_transform_layers(&maps))
+    }
+
+    /// The transform layers of [`CategoryGraph::path`] composed into one, so
+    /// it can be kept and used for any number of lookups.
+    pub fn composed_layer(self: &Self, from: &str, to: &str) -> Result<Vec<IntervalTransform>, GraphError> {
+        Ok(compose_all_transform_layers(&self.transform_layers(from, to)?))
+    }
+
+    /// Maps a number in category `from` to its number in category `to`.
+    pub fn map_point(self: &Self, from: &str, to: &str, point: i64) -> Result<i64, GraphError> {
+        let mut point = point;
+        for layer in self.transform_layers(from, to)? {
+            point = apply_transform_layer_to_point(&layer, point);
+        }
+
+        Ok(point)
+    }
+
+    /// Maps ranges of numbers in category `from` to the ranges they cover in category `to`.
+    pub fn map_intervals(self: &Self, from: &str, to: &str, intervals: &Vec<Interval>) -> Result<Vec<Interval>, GraphError> {
+        let mut intervals = intervals.iter().copied().collect::<IntervalSet>();
+        for layer in self.transform_layers(from, to)? {
+            intervals = apply_transform_layer_to_interval_set(&layer, &intervals);
+        }
+
+        Ok(intervals.into_intervals())
+    }
+
+    /// Maps ranges of numbers in category `to` back to every range in
+    /// category `from` that ends up in them.
+    pub fn map_intervals_back(self: &Self, from: &str, to: &str, intervals: &Vec<Interval>) -> Result<Vec<Interval>, GraphError> {
+        Ok(invert_transform_layers_to_intervals(&self.transform_layers(from, to)?, intervals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_input;
+
+    fn map(source: &str, destination: &str) -> CategoryMap {
+        CategoryMap { source: source.to_string(), destination: destination.to_string(), entries: vec![] }
+    }
+
+    #[test]
+    fn path_between_any_categories() {
+        let farm_maps = parse_input(include_str!("../sample_input.txt")).unwrap();
+        let graph = CategoryGraph::new(&farm_maps.maps).unwrap();
+
+        let path = graph.path("soil", "humidity").unwrap();
+        let categories = path.iter().map(|map| map.destination.as_str()).collect::<Vec<&str>>();
+        assert_eq!(categories, vec!["fertilizer", "water", "light", "temperature", "humidity"]);
+
+        assert_eq!(graph.path("seed", "light").unwrap().len(), 4);
+        assert_eq!(graph.path("water", "water").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn map_point_follows_sample_almanac() {
+        let farm_maps = parse_input(include_str!("../sample_input.txt")).unwrap();
+        let graph = CategoryGraph::new(&farm_maps.maps).unwrap();
+
+        // Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82
+        assert_eq!(graph.map_point("seed", "location", 79), Ok(82));
+        assert_eq!(graph.map_point("seed", "light", 79), Ok(74));
+        assert_eq!(graph.map_point("soil", "humidity", 81), Ok(78));
+
+        let intervals = graph.map_intervals("seed", "soil", &vec![Interval(79, 92)]).unwrap();
+        assert_eq!(intervals, vec![Interval(81, 94)]);
+    }
+
+    #[test]
+    fn composed_layer_matches_map_point() {
+        let farm_maps = parse_input(include_str!("../sample_input.txt")).unwrap();
+        let graph = CategoryGraph::new(&farm_maps.maps).unwrap();
+
+        let seed_to_location = graph.composed_layer("seed", "location").unwrap();
+        for seed in [79, 14, 55, 13] {
+            assert_eq!(Ok(apply_transform_layer_to_point(&seed_to_location, seed)), graph.map_point("seed", "location", seed));
+        }
+        assert_eq!(graph.composed_layer("water", "water"), Ok(vec![]));
+    }
+
+    #[test]
+    fn map_intervals_back_finds_the_seed_of_the_lowest_location() {
+        let farm_maps = parse_input(include_str!("../sample_input.txt")).unwrap();
+        let graph = CategoryGraph::new(&farm_maps.maps).unwrap();
+
+        // Seed 82 is the one in the seed ranges that reaches location 46
+        let seeds = graph.map_intervals_back("seed", "location", &vec![Interval(46, 46)]).unwrap();
+        assert!(seeds.iter().any(|interval| interval.contains(82)));
+        assert!(seeds.iter().all(|interval| graph.map_point("seed", "location", interval.0) == Ok(46)));
+    }
+
+    #[test]
+    fn map_point_through_empty_map_is_unchanged() {
+        let maps = vec![map("seed", "soil")];
+        let graph = CategoryGraph::new(&maps).unwrap();
+
+        assert_eq!(graph.map_point("seed", "soil", 42), Ok(42));
+    }
+
+    #[test]
+    fn path_reports_missing_and_ambiguous_paths() {
+        let maps = vec![map("seed", "soil"), map("soil", "water"), map("seed", "light"), map("light", "water")];
+        let graph = CategoryGraph::new(&maps).unwrap();
+
+        assert_eq!(graph.path("soil", "seed"), Err(GraphError::NoPath(String::from("soil"), String::from("seed"))));
+        assert_eq!(graph.path("seed", "water"), Err(GraphError::AmbiguousPath(String::from("seed"), String::from("water"))));
+        assert_eq!(graph.path("seed", "soil").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn new_reports_cycles() {
+        let maps = vec![map("seed", "soil"), map("soil", "water"), map("water", "light"), map("light", "soil")];
+
+        let error = CategoryGraph::new(&maps).unwrap_err();
+
+        assert_eq!(error, GraphError::Cycle(vec![
+            String::from("soil"), String::from("water"), String::from("light"), String::from("soil")
+        ]));
+        assert_eq!(error.to_string(), "the maps loop through `soil` -> `water` -> `light` -> `soil`");
+    }
+}
@@ -56,6 +56,142 @@ impl Interval {
     }
 }
 
+/// A set of points, kept as sorted, non-overlapping intervals with any that
+/// touch merged into one. This keeps the number of intervals as small as it
+/// can be, however many are added to it.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { intervals: Vec::new() }
+    }
+
+    pub fn intervals(self: &Self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn into_intervals(self: Self) -> Vec<Interval> {
+        self.intervals
+    }
+
+    pub fn is_empty(self: &Self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn insert(self: &mut Self, interval: Interval) {
+        let intervals = std::mem::take(&mut self.intervals);
+        *self = intervals.into_iter().chain([interval]).collect();
+    }
+
+    pub fn contains(self: &Self, point: i64) -> bool {
+        let index = self.intervals.partition_point(|interval| interval.1 < point);
+        index < self.intervals.len() && self.intervals[index].contains(point)
+    }
+
+    pub fn min(self: &Self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.0)
+    }
+
+    pub fn max(self: &Self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.1)
+    }
+
+    /// The number of points in the set.
+    pub fn total_length(self: &Self) -> i64 {
+        self.intervals.iter()
+            .map(|interval| interval.1 - interval.0 + 1)
+            .sum()
+    }
+
+    pub fn union(self: &Self, other: &IntervalSet) -> IntervalSet {
+        self.intervals.iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(self: &Self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::<Interval>::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+
+            let start = a.0.max(b.0);
+            let end = a.1.min(b.1);
+            if start <= end {
+                intervals.push(Interval(start, end));
+            }
+
+            if a.1 < b.1 { i += 1 } else { j += 1 }
+        }
+
+        // Both sets have gaps between their intervals, so the pieces do too
+        IntervalSet { intervals }
+    }
+
+    /// The points in this set that are not in `other`.
+    pub fn difference(self: &Self, other: &IntervalSet) -> IntervalSet {
+        match (self.min(), self.max()) {
+            (Some(min), Some(max)) => self.intersection(&other.complement_within(Interval(min, max))),
+            _ => IntervalSet::new()
+        }
+    }
+
+    /// The points within `bounds` that are not in this set.
+    pub fn complement_within(self: &Self, bounds: Interval) -> IntervalSet {
+        let mut intervals = Vec::<Interval>::new();
+        let mut start = bounds.0;
+
+        for interval in self.intervals.iter() {
+            if interval.1 < start {
+                continue;
+            }
+            if interval.0 > bounds.1 {
+                break;
+            }
+
+            if interval.0 > start {
+                intervals.push(Interval(start, interval.0 - 1));
+            }
+            if interval.1 >= bounds.1 {
+                return IntervalSet { intervals };
+            }
+            start = interval.1 + 1;
+        }
+
+        if start <= bounds.1 {
+            intervals.push(Interval(start, bounds.1));
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    /// Collects intervals in any order, skipping empty ones (whose end is
+    /// before their start).
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> IntervalSet {
+        let mut sorted = iter.into_iter()
+            .filter(|interval| interval.0 <= interval.1)
+            .collect::<Vec<Interval>>();
+        sorted.sort_by_key(|interval| interval.0);
+
+        let mut intervals = Vec::<Interval>::new();
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if interval.0 <= last.1.saturating_add(1) => last.1 = last.1.max(interval.1),
+                _ => intervals.push(interval),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,4 +500,71 @@ mod tests {
         assert_eq!(result.intersection, Some(Interval(8, 8)));
         assert_eq!(result.consequent, Some(Interval(9, 10)));
     }
-}
\ No newline at end of file
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|(start, end)| Interval(*start, *end)).collect()
+    }
+
+    #[test]
+    fn interval_set_sorts_and_merges_overlapping_and_adjacent_intervals() {
+        let intervals = set(&[(20, 25), (0, 4), (5, 9), (3, 7), (30, 29), (22, 30)]);
+
+        assert_eq!(intervals.intervals(), &[Interval(0, 9), Interval(20, 30)]);
+    }
+
+    #[test]
+    fn interval_set_insert_keeps_set_normalized() {
+        let mut intervals = set(&[(0, 4), (10, 14)]);
+
+        intervals.insert(Interval(5, 9));
+
+        assert_eq!(intervals, set(&[(0, 14)]));
+    }
+
+    #[test]
+    fn interval_set_contains_min_max_and_total_length() {
+        let intervals = set(&[(-5, -1), (10, 14)]);
+
+        assert!(intervals.contains(-5));
+        assert!(intervals.contains(12));
+        assert!(!intervals.contains(0));
+        assert!(!intervals.contains(15));
+        assert_eq!(intervals.min(), Some(-5));
+        assert_eq!(intervals.max(), Some(14));
+        assert_eq!(intervals.total_length(), 10);
+
+        assert_eq!(IntervalSet::new().min(), None);
+        assert_eq!(IntervalSet::new().total_length(), 0);
+    }
+
+    #[test]
+    fn interval_set_union() {
+        let union = set(&[(0, 4), (20, 24)]).union(&set(&[(5, 9), (22, 30)]));
+
+        assert_eq!(union, set(&[(0, 9), (20, 30)]));
+    }
+
+    #[test]
+    fn interval_set_intersection() {
+        let intersection = set(&[(0, 10), (20, 30)]).intersection(&set(&[(5, 25), (30, 40)]));
+
+        assert_eq!(intersection.intervals(), &[Interval(5, 10), Interval(20, 25), Interval(30, 30)]);
+    }
+
+    #[test]
+    fn interval_set_difference() {
+        let difference = set(&[(0, 10), (20, 30)]).difference(&set(&[(5, 25), (30, 40)]));
+
+        assert_eq!(difference.intervals(), &[Interval(0, 4), Interval(26, 29)]);
+        assert_eq!(IntervalSet::new().difference(&set(&[(0, 1)])), IntervalSet::new());
+    }
+
+    #[test]
+    fn interval_set_complement_within_bounds() {
+        let intervals = set(&[(0, 4), (10, 14), (30, 40)]);
+
+        assert_eq!(intervals.complement_within(Interval(2, 35)).intervals(), &[Interval(5, 9), Interval(15, 29)]);
+        assert_eq!(intervals.complement_within(Interval(-3, 2)).intervals(), &[Interval(-3, -1)]);
+        assert_eq!(intervals.complement_within(Interval(41, 45)).intervals(), &[Interval(41, 45)]);
+    }
+}
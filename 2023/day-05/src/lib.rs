@@ -0,0 +1,237 @@
+pub mod graph;
+pub mod intervals;
+mod parser;
+pub mod transform;
+
+use aoc_core::{ParseError, Solution};
+
+use crate::intervals::*;
+use crate::parser::*;
+use crate::transform::*;
+
+pub use crate::parser::{parse_almanac, CategoryMap, FarmMaps, MapEntry, LOCATION_CATEGORY, SEED_CATEGORY};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = FarmMaps;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<FarmMaps, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(farm_maps: &FarmMaps) -> i64 {
+        let transform_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+        let seed_to_location = compose_all_transform_layers(&transform_layers);
+
+        part1(&farm_maps.seeds, &vec![seed_to_location])
+    }
+
+    fn part_two(farm_maps: &FarmMaps) -> Option<i64> {
+        let seed_intervals = map_seed_pairs_to_intervals(&farm_maps.seeds);
+        let transform_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+
+        Some(partx(&seed_intervals, &transform_layers))
+    }
+}
+
+fn part1(seeds: &Vec<i64>, transform_layers: &Vec<Vec<IntervalTransform>>) -> i64 {
+    seeds.iter().map(|seed| {
+        let mut location = *seed;
+        for layer in transform_layers.iter() {
+            location = apply_transform_layer_to_point(&layer, location);
+        }
+        
+        location
+    })
+    .min()
+    .unwrap()
+}
+
+fn partx(seed_intervals: &[Interval], transform_layers: &Vec<Vec<IntervalTransform>>) -> i64 {
+    let mut location_intervals = seed_intervals
+        .iter()
+        .copied()
+        .collect::<IntervalSet>();
+
+    for layer in transform_layers.iter() {
+        location_intervals = apply_transform_layer_to_interval_set(&layer, &location_intervals);
+
+        assert!(!location_intervals.is_empty());
+    }
+
+    location_intervals.min().unwrap()
+}
+
+fn map_seed_pairs_to_intervals(seeds: &[i64]) -> Vec<Interval> {
+    (0..seeds.len()/2)
+        .map(|i| {
+            let index = i * 2;
+            let start = seeds[index];
+            let end = seeds[index] + seeds[index + 1] - 1;
+            Interval(start, end)
+        })
+        .collect::<Vec<Interval>>()
+}
+
+fn to_interval_transform(map_entry: &MapEntry) -> IntervalTransform {
+    IntervalTransform {
+        interval: Interval(map_entry.source_start, map_entry.source_start + map_entry.range - 1),
+        addend: map_entry.destination_start - map_entry.source_start
+    }   
+}
+
+fn convert_maps_to_transform_layers(maps: &Vec<&Vec<MapEntry>>) -> Vec<Vec<IntervalTransform>> {
+    let layers = maps
+        .iter()
+        .map(|m| 
+            m.iter().map(to_interval_transform).collect::<Vec<IntervalTransform>>())
+        .collect::<Vec<Vec<IntervalTransform>>>();
+
+    for layer in layers.iter() {
+        for i in 0..layer.len().saturating_sub(1) {
+            assert!(layer[i].interval.1 < layer[i+1].interval.0);
+        }
+    }
+
+    layers
+}
+
+#[cfg(test)]
+fn map_each_seed_to_trivial_interval(seeds: &Vec<i64>) -> Vec<Interval>{
+    seeds.iter()
+        .map(|seed| Interval(*seed, *seed + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1_with_sample() {
+        let sample_input = include_str!("../sample_input.txt");
+
+        let farm_maps = parse_input(sample_input).unwrap();
+        let seeds = &farm_maps.seeds;
+        let transformation_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+
+        let result = part1(&seeds, &transformation_layers);
+
+        assert_eq!(result, 35);
+    }
+
+    #[test]
+    fn composed_layer_maps_sample_like_every_layer() {
+        let sample_input = include_str!("../sample_input.txt");
+
+        let farm_maps = parse_input(sample_input).unwrap();
+        let transformation_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+        let composed = compose_all_transform_layers(&transformation_layers);
+
+        for seed in 0..=100 {
+            assert_eq!(part1(&vec![seed], &vec![composed.clone()]), part1(&vec![seed], &transformation_layers), "{}", seed);
+        }
+    }
+
+    #[test]
+    fn inverted_layers_round_trip_on_sample() {
+        let sample_input = include_str!("../sample_input.txt");
+
+        let farm_maps = parse_input(sample_input).unwrap();
+        let transformation_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+        let composed = compose_all_transform_layers(&transformation_layers);
+
+        for seed in 0..=100 {
+            let location = part1(&vec![seed], &transformation_layers);
+            let seeds = invert_transform_layers_to_intervals(&transformation_layers, &vec![Interval(location, location)]);
+
+            assert!(seeds.iter().any(|interval| interval.contains(seed)), "{}", seed);
+            assert_eq!(invert_transform_layer_to_point(&composed, location), seeds);
+        }
+
+        let locations = vec![Interval(40, 60), Interval(90, 99)];
+        let seeds = invert_transform_layers_to_intervals(&transformation_layers, &locations);
+        assert_eq!(invert_transform_layer_to_intervals(&composed, &locations), seeds);
+        for seed in -10..=110 {
+            let location = part1(&vec![seed], &transformation_layers);
+            let in_seeds = seeds.iter().any(|interval| interval.contains(seed));
+            assert_eq!(in_seeds, locations.iter().any(|interval| interval.contains(location)), "{}", seed);
+        }
+    }
+
+    #[test]
+    fn test_part1_as_interval_with_sample() {
+        let sample_input = include_str!("../sample_input.txt");
+
+        let farm_maps = parse_input(sample_input).unwrap();
+
+        let seed_intervals = map_each_seed_to_trivial_interval(&farm_maps.seeds);
+        let transformation_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+
+        let result = partx(&seed_intervals, &transformation_layers);
+
+        assert_eq!(result, 35);
+    }
+    
+    #[test]
+    fn test_part2_with_sample() {
+        let sample_input = include_str!("../sample_input.txt");
+
+        let farm_maps = parse_input(sample_input).unwrap();
+
+        let seed_intervals = map_seed_pairs_to_intervals(&farm_maps.seeds);
+        let transformation_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+
+        let result = partx(&seed_intervals, &transformation_layers);
+
+        assert_eq!(result, 46);
+    }
+
+    #[test]
+    fn solves_puzzle_input() {
+        let input = include_str!("../part1.txt");
+
+        let farm_maps = Day05::parse(input).unwrap();
+        assert_eq!(farm_maps.seeds.len(), 20);
+        assert_eq!(farm_maps.map("seed", "soil").unwrap().entries.len(), 17);
+        assert_eq!(farm_maps.map("soil", "fertilizer").unwrap().entries.len(), 9);
+        assert_eq!(farm_maps.map("fertilizer", "water").unwrap().entries.len(), 40);
+        assert_eq!(farm_maps.map("water", "light").unwrap().entries.len(), 24);
+        assert_eq!(farm_maps.map("light", "temperature").unwrap().entries.len(), 20);
+        assert_eq!(farm_maps.map("temperature", "humidity").unwrap().entries.len(), 44);
+        assert_eq!(farm_maps.map("humidity", "location").unwrap().entries.len(), 41);
+
+        assert_eq!(Day05::part_one(&farm_maps), 806029445);
+
+        let seed_intervals = map_each_seed_to_trivial_interval(&farm_maps.seeds);
+        assert_eq!(seed_intervals.len(), 20);
+        let transform_layers = convert_maps_to_transform_layers(&farm_maps.collect());
+        assert_eq!(partx(&seed_intervals, &transform_layers), 806029445);
+
+        assert_eq!(Day05::part_two(&farm_maps), Some(59370572));
+    }
+
+    #[test]
+    fn to_interval_transform_works() {
+        assert_eq!(
+            to_interval_transform(&MapEntry::new(50, 98, 2)), 
+            IntervalTransform{
+                interval: Interval(98, 99),
+                addend: -48
+            }
+        );
+
+        assert_eq!(
+            to_interval_transform(&MapEntry::new(52, 50, 48)), 
+            IntervalTransform{
+                interval: Interval(50, 97),
+                addend: 2
+            }
+        );
+    }
+}
\ No newline at end of file
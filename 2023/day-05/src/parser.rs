@@ -1,25 +1,49 @@
+use std::iter::Peekable;
+
+use aoc_core::{parse_number, ParseError};
+
+use crate::graph::{CategoryGraph, GraphError};
+
+/// The category the almanac's `seeds:` line lists.
+pub const SEED_CATEGORY: &str = "seed";
+
+/// The category both parts of the puzzle want seeds mapped to.
+pub const LOCATION_CATEGORY: &str = "location";
+
+#[derive(Debug)]
 pub struct FarmMaps {
     pub seeds: Vec<i64>,
-    pub seed_to_soil_map: Vec<MapEntry>,
-    pub soil_to_fertilizer_map: Vec<MapEntry>,
-    pub fertilizer_to_water_map: Vec<MapEntry>,
-    pub water_to_light_map: Vec<MapEntry>,
-    pub light_to_temperature_map: Vec<MapEntry>,
-    pub temperature_to_humidity_map: Vec<MapEntry>,
-    pub humidity_to_location_map: Vec<MapEntry>
+    /// Every map in the almanac, in the order they appear.
+    pub maps: Vec<CategoryMap>,
+    /// The category the maps were checked to lead seeds to.
+    pub target: String,
+}
+
+/// One `<source>-to-<destination> map:` section of the almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub source: String,
+    pub destination: String,
+    pub entries: Vec<MapEntry>,
 }
 
 impl FarmMaps {
+    pub fn map(self: &Self, source: &str, destination: &str) -> Option<&CategoryMap> {
+        self.maps.iter().find(|map| map.source == source && map.destination == destination)
+    }
+
+    /// The maps that take seeds to `target`, in the order to apply them.
+    pub fn chain(self: &Self, target: &str) -> Result<Vec<&CategoryMap>, GraphError> {
+        CategoryGraph::new(&self.maps)?.path(SEED_CATEGORY, target)
+    }
+
+    /// The entries of each map from seeds to the target the almanac was parsed for.
     pub fn collect(self: &Self) -> Vec<&Vec<MapEntry>> {
-        vec![
-            &self.seed_to_soil_map,
-            &self.soil_to_fertilizer_map,
-            &self.fertilizer_to_water_map,
-            &self.water_to_light_map,
-            &self.light_to_temperature_map,
-            &self.temperature_to_humidity_map,
-            &self.humidity_to_location_map
-        ]
+        self.chain(&self.target)
+            .expect("parse_almanac checks the maps lead to the target")
+            .into_iter()
+            .map(|map| &map.entries)
+            .collect()
     }
 }
 
@@ -36,148 +60,128 @@ impl MapEntry {
     }
 }
 
-pub fn parse_input(input: &str) -> FarmMaps {
-    let mut lines = input.lines().filter(|l| !l.is_empty());
-
-    let seeds = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .skip(1)
-        .map(|part| part.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
-
-    let mut header = lines.next().unwrap();
-
-    assert_eq!(header, "seed-to-soil map:");
-    let mut seed_to_soil_map = Vec::<MapEntry>::new();
-
-    loop {
-        let line = lines.next().unwrap();
+/// Parses an almanac whose maps lead seeds to locations.
+pub fn parse_input(input: &str) -> Result<FarmMaps, ParseError> {
+    parse_almanac(input, LOCATION_CATEGORY)
+}
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            seed_to_soil_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
+/// Parses an almanac with any number of maps, in any order, and checks that
+/// they lead seeds to `target`.
+pub fn parse_almanac(input: &str, target: &str) -> Result<FarmMaps, ParseError> {
+    let mut lines = input.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.is_empty())
+        .peekable();
+
+    let (line_number, line) = lines.next()
+        .ok_or_else(|| ParseError::end_of_input(input, "expected `seeds:`"))?;
+    let seeds = parse_seeds(line_number, line)?;
+
+    let mut maps = Vec::<CategoryMap>::new();
+    while let Some((line_number, line)) = lines.next() {
+        let map = parse_map(line_number, line, &mut lines)?;
+
+        if maps.iter().any(|other| other.source == map.source && other.destination == map.destination) {
+            return Err(ParseError::line(line_number, line, "this map has already been given"));
         }
+        maps.push(map);
     }
-    seed_to_soil_map.sort();
-
-    assert_eq!(header, "soil-to-fertilizer map:");
-    let mut soil_to_fertilizer_map = Vec::<MapEntry>::new();
 
-    loop {
-        let line = lines.next().unwrap();
+    let farm_maps = FarmMaps { seeds, maps, target: target.to_string() };
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            soil_to_fertilizer_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
-        }
+    if let Err(e) = farm_maps.chain(target) {
+        return Err(ParseError::end_of_input(input, &e.to_string()));
     }
-    soil_to_fertilizer_map.sort();
 
-    assert_eq!(header, "fertilizer-to-water map:");
-    let mut fertilizer_to_water_map = Vec::<MapEntry>::new();
+    Ok(farm_maps)
+}
 
-    loop {
-        let line = lines.next().unwrap();
+/// Parses the `seeds:` line, which has to hold pairs of a start and a length
+/// so that both parts can read it.
+fn parse_seeds(line_number: usize, line: &str) -> Result<Vec<i64>, ParseError> {
+    let numbers = line.strip_prefix("seeds:")
+        .ok_or_else(|| ParseError::line(line_number, line, "expected `seeds:`"))?;
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            fertilizer_to_water_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
-        }
-    }
-    fertilizer_to_water_map.sort();
+    let parts = numbers.split_whitespace().collect::<Vec<&str>>();
 
-    assert_eq!(header, "water-to-light map:");
-    let mut water_to_light_map = Vec::<MapEntry>::new();
+    if parts.is_empty() {
+        return Err(ParseError::line(line_number, line, "expected at least one seed"));
+    }
+    if parts.len() % 2 != 0 {
+        return Err(ParseError::line(line_number, line, "expected seeds in pairs of a start and a length"));
+    }
 
-    loop {
-        let line = lines.next().unwrap();
+    let seeds = parts.iter()
+        .map(|part| parse_number::<i64>(line_number, line, part))
+        .collect::<Result<Vec<i64>, ParseError>>()?;
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            water_to_light_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
+    for (part, length) in parts.iter().zip(seeds.iter()).skip(1).step_by(2) {
+        if *length < 1 {
+            return Err(ParseError::at(line_number, line, part, "expected a length of at least 1"));
         }
     }
-    water_to_light_map.sort();
 
-    assert_eq!(header, "light-to-temperature map:");
-    let mut light_to_temperature_map = Vec::<MapEntry>::new();
+    Ok(seeds)
+}
 
-    loop {
-        let line = lines.next().unwrap();
+/// Parses a `<source>-to-<destination> map:` header line followed by the map's
+/// entries, which run until the next line that does not start with a digit.
+fn parse_map<'a>(
+    line_number: usize,
+    line: &str,
+    lines: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>)
+    -> Result<CategoryMap, ParseError>
+{
+    let (source, destination) = parse_header(line_number, line)?;
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            light_to_temperature_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
-        }
-    }
-    light_to_temperature_map.sort();
+    let mut entries = Vec::<(MapEntry, usize, &str)>::new();
 
-    assert_eq!(header, "temperature-to-humidity map:");
-    let mut temperature_to_humidity_map = Vec::<MapEntry>::new();
+    while let Some((line_number, line)) = lines.next_if(|(_, line)| line.starts_with(|c: char| c.is_ascii_digit())) {
+        entries.push((parse_line(line_number, line)?, line_number, line));
+    }
+    entries.sort();
 
-    loop {
-        let line = lines.next().unwrap();
+    for pair in entries.windows(2) {
+        let (previous, (entry, line_number, line)) = (&pair[0].0, &pair[1]);
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            temperature_to_humidity_map.push(parse_line(line));
-        } else {
-            header = line;
-            break;
+        if previous.source_start + previous.range > entry.source_start {
+            return Err(ParseError::line(*line_number, line, "this entry overlaps another entry of the map"));
         }
     }
-    temperature_to_humidity_map.sort();
 
-    assert_eq!(header, "humidity-to-location map:");
-    let mut humidity_to_location_map = Vec::<MapEntry>::new();
+    let entries = entries.into_iter()
+        .map(|(entry, _, _)| entry)
+        .collect();
 
-    loop {
-        let next_line = lines.next();
+    Ok(CategoryMap { source: source.to_string(), destination: destination.to_string(), entries })
+}
 
-        if next_line == None {
-            break;
-        }
+fn parse_header(line_number: usize, line: &str) -> Result<(&str, &str), ParseError> {
+    let is_category = |category: &str| !category.is_empty() && category.chars().all(|c| c.is_ascii_lowercase());
+
+    line.strip_suffix(" map:")
+        .and_then(|categories| categories.split_once("-to-"))
+        .filter(|(source, destination)| is_category(source) && is_category(destination))
+        .ok_or_else(|| ParseError::line(line_number, line, "expected a `<source>-to-<destination> map:` header"))
+}
 
-        let line = next_line.unwrap();
+fn parse_line(line_number: usize, line: &str) -> Result<MapEntry, ParseError> {
+    let parts = line.split_whitespace().collect::<Vec<&str>>();
 
-        if line.chars().nth(0).unwrap().is_numeric() {
-            humidity_to_location_map.push(parse_line(line));
-        } else {
-            panic!("What?!");
-        }
+    if parts.len() != 3 {
+        return Err(ParseError::line(line_number, line, "expected three numbers"));
     }
-    humidity_to_location_map.sort();
 
-    FarmMaps {
-        seeds,
-        seed_to_soil_map,
-        soil_to_fertilizer_map,
-        fertilizer_to_water_map,
-        water_to_light_map,
-        light_to_temperature_map,
-        temperature_to_humidity_map,
-        humidity_to_location_map
-    }
-}
+    let destination_start = parse_number::<i64>(line_number, line, parts[0])?;
+    let source_start = parse_number::<i64>(line_number, line, parts[1])?;
+    let range = parse_number::<i64>(line_number, line, parts[2])?;
 
-fn parse_line(line: &str) -> MapEntry {
-    let parts = line.split(' ').collect::<Vec<&str>>();
-    let destination_start = parts[0].parse::<i64>().unwrap();
-    let source_start = parts[1].parse::<i64>().unwrap();
-    let range = parts[2].parse::<i64>().unwrap();
+    if range < 1 {
+        return Err(ParseError::at(line_number, line, parts[2], "expected a length of at least 1"));
+    }
 
-    MapEntry::new(destination_start, source_start, range)
+    Ok(MapEntry::new(destination_start, source_start, range))
 }
 
 #[cfg(test)]
@@ -190,51 +194,187 @@ mod tests {
     fn parses_sample_input() {
         let sample_input = include_str!("../sample_input.txt");
 
-        let farm_maps = parse_input(sample_input);
+        let farm_maps = parse_input(sample_input).unwrap();
 
         assert_eq!(farm_maps.seeds.len(), 4);
         assert_contains_exactly!(farm_maps.seeds, vec![79i64, 14i64, 55i64, 13i64]);
 
-        assert_eq!(farm_maps.seed_to_soil_map.len(), 2);
+        assert_eq!(farm_maps.map("seed", "soil").unwrap().entries.len(), 2);
         assert_contains_exactly!(
-            farm_maps.seed_to_soil_map,
+            farm_maps.map("seed", "soil").unwrap().entries,
             vec![MapEntry::new(52, 50, 48), MapEntry::new(50, 98, 2)]
         );
 
-        assert_eq!(farm_maps.soil_to_fertilizer_map.len(), 3);
+        assert_eq!(farm_maps.map("soil", "fertilizer").unwrap().entries.len(), 3);
         assert_contains_exactly!(
-            farm_maps.soil_to_fertilizer_map,
+            farm_maps.map("soil", "fertilizer").unwrap().entries,
             vec![MapEntry::new(39, 0, 15), MapEntry::new(0, 15, 37), MapEntry::new(37, 52, 2)]
         );
 
-        assert_eq!(farm_maps.fertilizer_to_water_map.len(), 4);
+        assert_eq!(farm_maps.map("fertilizer", "water").unwrap().entries.len(), 4);
         assert_contains_exactly!(
-            farm_maps.fertilizer_to_water_map,
+            farm_maps.map("fertilizer", "water").unwrap().entries,
             vec![MapEntry::new(42, 0, 7), MapEntry::new(57, 7, 4), MapEntry::new(0, 11, 42), MapEntry::new(49, 53, 8)]
         );
 
-        assert_eq!(farm_maps.water_to_light_map.len(), 2);
+        assert_eq!(farm_maps.map("water", "light").unwrap().entries.len(), 2);
         assert_contains_exactly!(
-            farm_maps.water_to_light_map,
+            farm_maps.map("water", "light").unwrap().entries,
             vec![MapEntry::new(88, 18, 7), MapEntry::new(18, 25, 70)]
         );
 
-        assert_eq!(farm_maps.light_to_temperature_map.len(), 3);
+        assert_eq!(farm_maps.map("light", "temperature").unwrap().entries.len(), 3);
         assert_contains_exactly!(
-            farm_maps.light_to_temperature_map,
+            farm_maps.map("light", "temperature").unwrap().entries,
             vec![MapEntry::new(81, 45, 19), MapEntry::new(68, 64, 13), MapEntry::new(45, 77, 23)]
         );
 
-        assert_eq!(farm_maps.temperature_to_humidity_map.len(), 2);
+        assert_eq!(farm_maps.map("temperature", "humidity").unwrap().entries.len(), 2);
         assert_contains_exactly!(
-            farm_maps.temperature_to_humidity_map,
+            farm_maps.map("temperature", "humidity").unwrap().entries,
             vec![MapEntry::new(1, 0, 69), MapEntry::new(0, 69, 1)]
         );
 
-        assert_eq!(farm_maps.humidity_to_location_map.len(), 2);
+        assert_eq!(farm_maps.map("humidity", "location").unwrap().entries.len(), 2);
         assert_contains_exactly!(
-            farm_maps.humidity_to_location_map,
+            farm_maps.map("humidity", "location").unwrap().entries,
             vec![MapEntry::new(60, 56, 37), MapEntry::new(56, 93, 4)]
         );
     }
+
+    #[test]
+    fn reports_malformed_numbers() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 5O 48\n";
+
+        let error = parse_input(input).unwrap_err();
+
+        assert_eq!(error, ParseError {
+            line: 5,
+            column: 4,
+            text: String::from("5O"),
+            message: String::from("expected a number"),
+        });
+    }
+
+    #[test]
+    fn reports_malformed_header() {
+        let sample_input = include_str!("../sample_input.txt")
+            .replace("water-to-light map:", "water to light map:");
+
+        let error = parse_input(&sample_input).unwrap_err();
+
+        assert_eq!((error.line, error.column), (18, 1));
+        assert_eq!(error.text, "water to light map:");
+        assert_eq!(error.message, "expected a `<source>-to-<destination> map:` header");
+    }
+
+    #[test]
+    fn reports_missing_maps() {
+        let error = parse_input("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.message, "no maps lead from `seed` to `location`");
+    }
+
+    #[test]
+    fn reports_broken_chain() {
+        let sample_input = include_str!("../sample_input.txt")
+            .replace("water-to-light map:", "water-to-lite map:");
+
+        let error = parse_input(&sample_input).unwrap_err();
+
+        assert_eq!(error.message, "no maps lead from `seed` to `location`");
+    }
+
+    #[test]
+    fn reports_repeated_maps() {
+        let error = parse_input("seeds: 79 14\nseed-to-location map:\n1 2 3\nseed-to-location map:\n").unwrap_err();
+
+        assert_eq!((error.line, error.text.as_str()), (4, "seed-to-location map:"));
+        assert_eq!(error.message, "this map has already been given");
+    }
+
+    #[test]
+    fn parses_maps_in_any_order() {
+        let sample_input = include_str!("../sample_input.txt");
+        let (seeds, sections) = sample_input.split_once("\n\n").unwrap();
+        let mut sections = sections.trim_end().split("\n\n").collect::<Vec<&str>>();
+        sections.reverse();
+        let reordered_input = format!("{}\n\n{}", seeds, sections.join("\n\n"));
+
+        let farm_maps = parse_input(&reordered_input).unwrap();
+        let sample_maps = parse_input(sample_input).unwrap();
+
+        assert_eq!(farm_maps.maps.first().unwrap().source, "humidity");
+        assert_eq!(farm_maps.collect(), sample_maps.collect());
+    }
+
+    #[test]
+    fn parses_extended_almanac_to_any_target() {
+        let input = "seeds: 1 2
+
+seed-to-soil map:
+10 0 5
+
+soil-to-moisture map:
+20 10 5
+
+moisture-to-location map:
+0 20 5
+";
+
+        let farm_maps = parse_input(input).unwrap();
+        let chain = farm_maps.chain(LOCATION_CATEGORY).unwrap();
+        let categories = chain.iter().map(|map| map.destination.as_str()).collect::<Vec<&str>>();
+        assert_eq!(categories, vec!["soil", "moisture", "location"]);
+
+        let farm_maps = parse_almanac(input, "moisture").unwrap();
+        assert_eq!(farm_maps.collect().len(), 2);
+
+        let error = parse_almanac(input, "water").unwrap_err();
+        assert_eq!(error.message, "no maps lead from `seed` to `water`");
+    }
+
+    #[test]
+    fn reports_looping_maps() {
+        let input = "seeds: 79 14\nseed-to-soil map:\nsoil-to-seed map:\nsoil-to-location map:\n";
+
+        let error = parse_input(input).unwrap_err();
+
+        assert_eq!(error.message, "the maps loop through `seed` -> `soil` -> `seed`");
+    }
+
+    #[test]
+    fn reports_overlapping_entries() {
+        let error = parse_input("seeds: 79 14\nseed-to-location map:\n10 0 5\n20 3 5\n").unwrap_err();
+
+        assert_eq!((error.line, error.text.as_str()), (4, "20 3 5"));
+        assert_eq!(error.message, "this entry overlaps another entry of the map");
+    }
+
+    #[test]
+    fn reports_empty_ranges() {
+        let error = parse_input("seeds: 79 14\nseed-to-location map:\n10 0 0\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.message, "expected a length of at least 1");
+
+        let error = parse_input("seeds: 1 0\nseed-to-location map:\n10 0 5\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 10));
+        assert_eq!(error.message, "expected a length of at least 1");
+    }
+
+    #[test]
+    fn reports_missing_and_unpaired_seeds() {
+        let error = parse_input("seeds:\nseed-to-location map:\n10 0 5\n").unwrap_err();
+
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "expected at least one seed");
+
+        let error = parse_input("seeds: 79 14 55\nseed-to-location map:\n10 0 5\n").unwrap_err();
+
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "expected seeds in pairs of a start and a length");
+    }
 }
@@ -76,6 +76,135 @@ pub fn apply_transform_layer_to_intervals(layer: &Vec<IntervalTransform>, interv
         .collect()
 }
 
+/// Applies `layer` to every interval in `intervals`, keeping the result
+/// normalized so it does not grow from one layer to the next.
+pub fn apply_transform_layer_to_interval_set(layer: &Vec<IntervalTransform>, intervals: &IntervalSet) -> IntervalSet {
+    intervals.intervals()
+        .iter()
+        .flat_map(|interval| apply_transform_layer_to_interval(layer, *interval))
+        .collect()
+}
+
+/// Splits `interval` into the pieces each transform in `layer` covers, and
+/// the pieces between them, which are left as they are (an addend of 0).
+/// The layer has to be sorted.
+fn split_by_layer(layer: &[IntervalTransform], interval: Interval) -> Vec<(Interval, i64)> {
+    let mut pieces = Vec::<(Interval, i64)>::new();
+    let mut start = interval.0;
+
+    for transform in layer.iter() {
+        if transform.interval.1 < start {
+            continue;
+        }
+        if transform.interval.0 > interval.1 {
+            break;
+        }
+
+        if transform.interval.0 > start {
+            pieces.push((Interval(start, transform.interval.0 - 1), 0));
+        }
+
+        let end = transform.interval.1.min(interval.1);
+        pieces.push((Interval(start.max(transform.interval.0), end), transform.addend));
+
+        if end == interval.1 {
+            return pieces;
+        }
+        start = end + 1;
+    }
+
+    pieces.push((Interval(start, interval.1), 0));
+    pieces
+}
+
+/// Combines two layers into one that maps every point the way applying
+/// `first` and then `second` does. Both layers have to be sorted; so is the
+/// result, with no overlaps, no transforms that leave points unchanged, and
+/// neighbouring transforms with the same addend merged.
+pub fn compose_transform_layers(first: &Vec<IntervalTransform>, second: &Vec<IntervalTransform>) -> Vec<IntervalTransform> {
+    let mut composed = Vec::<IntervalTransform>::new();
+
+    for (source, first_addend) in split_by_layer(first, Interval(i64::MIN, i64::MAX)) {
+        let image = Interval(source.0 + first_addend, source.1 + first_addend);
+
+        for (piece, second_addend) in split_by_layer(second, image) {
+            let addend = first_addend + second_addend;
+            if addend == 0 {
+                continue;
+            }
+
+            let interval = Interval(piece.0 - first_addend, piece.1 - first_addend);
+            match composed.last_mut() {
+                Some(last) if last.addend == addend && last.interval.1 + 1 == interval.0 => {
+                    last.interval.1 = interval.1;
+                }
+                _ => composed.push(IntervalTransform { interval, addend }),
+            }
+        }
+    }
+
+    // Pieces come out in order of where `first` sends them, not where they start
+    composed.sort_by_key(|transform| transform.interval.0);
+
+    let mut merged = Vec::<IntervalTransform>::new();
+    for transform in composed {
+        match merged.last_mut() {
+            Some(last) if last.addend == transform.addend && last.interval.1 + 1 == transform.interval.0 => {
+                last.interval.1 = transform.interval.1;
+            }
+            _ => merged.push(transform),
+        }
+    }
+
+    merged
+}
+
+/// Composes every layer, in order, into a single layer.
+pub fn compose_all_transform_layers(layers: &Vec<Vec<IntervalTransform>>) -> Vec<IntervalTransform> {
+    layers.iter()
+        .fold(Vec::new(), |composed, layer| compose_transform_layers(&composed, layer))
+}
+
+/// Finds every point that `layer` maps into `interval`: the intervals each
+/// transform sends there, plus the part of `interval` no transform covers.
+/// The layer has to be sorted; the result is normalized as an [`IntervalSet`] is.
+pub fn invert_transform_layer_to_interval(layer: &Vec<IntervalTransform>, interval: Interval) -> Vec<Interval> {
+    let mut sources = split_by_layer(layer, interval)
+        .into_iter()
+        .filter(|(_, addend)| *addend == 0)
+        .map(|(piece, _)| piece)
+        .collect::<Vec<Interval>>();
+
+    for transform in layer.iter() {
+        let start = (interval.0 - transform.addend).max(transform.interval.0);
+        let end = (interval.1 - transform.addend).min(transform.interval.1);
+        if start <= end {
+            sources.push(Interval(start, end));
+        }
+    }
+
+    sources.into_iter().collect::<IntervalSet>().into_intervals()
+}
+
+/// Finds every point that `layer` maps to `point`.
+pub fn invert_transform_layer_to_point(layer: &Vec<IntervalTransform>, point: i64) -> Vec<Interval> {
+    invert_transform_layer_to_interval(layer, Interval(point, point))
+}
+
+pub fn invert_transform_layer_to_intervals(layer: &Vec<IntervalTransform>, intervals: &Vec<Interval>) -> Vec<Interval> {
+    intervals.iter()
+        .flat_map(|interval| invert_transform_layer_to_interval(layer, *interval))
+        .collect::<IntervalSet>()
+        .into_intervals()
+}
+
+/// Finds every point that applying all of `layers`, in order, maps into `intervals`.
+pub fn invert_transform_layers_to_intervals(layers: &Vec<Vec<IntervalTransform>>, intervals: &Vec<Interval>) -> Vec<Interval> {
+    layers.iter()
+        .rev()
+        .fold(intervals.clone(), |intervals, layer| invert_transform_layer_to_intervals(layer, &intervals))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,4 +234,67 @@ mod tests {
         assert_eq!(out_intervals[1], Interval(60, 60));
         assert_eq!(out_intervals[2], Interval(46, 55));
     }
-}
\ No newline at end of file
+
+    fn layer(transforms: &[(i64, i64, i64)]) -> Vec<IntervalTransform> {
+        transforms.iter()
+            .map(|(start, end, addend)| IntervalTransform { interval: Interval(*start, *end), addend: *addend })
+            .collect()
+    }
+
+    fn is_sorted_without_overlaps(layer: &Vec<IntervalTransform>) -> bool {
+        layer.windows(2).all(|pair| pair[0].interval.1 < pair[1].interval.0)
+    }
+
+    #[test]
+    fn compose_transform_layers_maps_like_both_layers() {
+        let first = layer(&[(0, 9, 20), (15, 19, -15), (30, 39, 5)]);
+        let second = layer(&[(5, 24, 100), (35, 44, -35)]);
+
+        let composed = compose_transform_layers(&first, &second);
+
+        assert!(is_sorted_without_overlaps(&composed));
+        for point in -10..60 {
+            let expected = apply_transform_layer_to_point(&second, apply_transform_layer_to_point(&first, point));
+            assert_eq!(apply_transform_layer_to_point(&composed, point), expected, "{}", point);
+        }
+    }
+
+    #[test]
+    fn compose_transform_layers_drops_and_merges_transforms() {
+        // 0..=4 and 5..=9 both end up 10 higher; 30..=39 goes down 10 and straight back up
+        let first = layer(&[(0, 4, 5), (5, 9, 10), (30, 39, -10)]);
+        let second = layer(&[(5, 9, 5), (20, 29, 10)]);
+
+        let composed = compose_transform_layers(&first, &second);
+
+        assert_eq!(composed, layer(&[(0, 9, 10), (20, 29, 10)]));
+    }
+
+    #[test]
+    fn compose_all_transform_layers_of_nothing_is_identity() {
+        assert_eq!(compose_all_transform_layers(&vec![]), vec![]);
+    }
+
+    #[test]
+    fn invert_transform_layer_to_point_finds_every_source() {
+        // 50 is where 40 is sent, and is left alone by the layer as well
+        let layer = layer(&[(0, 9, 100), (40, 44, 10)]);
+
+        assert_eq!(invert_transform_layer_to_point(&layer, 50), vec![Interval(40, 40), Interval(50, 50)]);
+        assert_eq!(invert_transform_layer_to_point(&layer, 5), vec![]);
+        assert_eq!(invert_transform_layer_to_point(&layer, 105), vec![Interval(5, 5), Interval(105, 105)]);
+    }
+
+    #[test]
+    fn invert_transform_layer_to_interval_round_trips() {
+        let layer = layer(&[(0, 9, 20), (15, 19, -15), (30, 39, 5)]);
+        let target = Interval(3, 33);
+
+        let sources = invert_transform_layer_to_interval(&layer, target);
+
+        for point in -10..60 {
+            let in_sources = sources.iter().any(|source| source.contains(point));
+            assert_eq!(in_sources, target.contains(apply_transform_layer_to_point(&layer, point)), "{}", point);
+        }
+    }
+}
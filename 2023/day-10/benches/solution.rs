@@ -0,0 +1,15 @@
+use aoc_core::bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_10::Day10;
+
+fn solution(c: &mut Criterion) {
+    bench_solution::<Day10>(c, include_str!("../part1.txt"));
+}
+
+// Part two takes around two seconds, so take the fewest samples Criterion allows.
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solution
+}
+criterion_main!(benches);
@@ -1,4 +1,7 @@
+use aoc_core::ParseError;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)] //PartialOrd, Ord
+#[allow(non_camel_case_types)]
 pub enum TileType {
     N_E,
     N_S,
@@ -42,12 +45,54 @@ impl TileType {
 
 /// Rows are read from bottom to top so that the grid can be 
 /// treated like the first quadrant of the cartesian plane.
-pub fn parse_input(input: &str) -> Vec<Vec<TileType>> {
-    invert_x_and_y_indices(
-        input.lines()
-            .rev()
-            .map(|l| l.chars().map(char_to_tile).collect())
-            .collect())
+pub fn parse_input(input: &str) -> Result<Vec<Vec<TileType>>, ParseError> {
+    let rows = input.lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(index + 1, line))
+        .collect::<Result<Vec<Vec<TileType>>, ParseError>>()?;
+
+    if rows.is_empty() {
+        return Err(ParseError::end_of_input(input, "expected a map"));
+    }
+
+    for (index, line) in input.lines().enumerate() {
+        if line.len() != rows[0].len() {
+            return Err(ParseError::line(index + 1, line, &format!("expected a row {} tiles wide", rows[0].len())));
+        }
+    }
+
+    let (row, column) = rows.iter()
+        .enumerate()
+        .find_map(|(row, tiles)| tiles.iter().position(|tile| *tile == TileType::Start).map(|column| (row, column)))
+        .ok_or_else(|| ParseError::end_of_input(input, "expected a start tile `S`"))?;
+
+    if count_connections(&rows, row, column) != 2 {
+        let line = input.lines().nth(row).unwrap();
+        return Err(ParseError::at(row + 1, line, &line[column..column + 1], "expected the start tile to connect to exactly two pipes"));
+    }
+
+    Ok(invert_x_and_y_indices(rows.into_iter().rev().collect()))
+}
+
+fn parse_line(line_number: usize, line: &str) -> Result<Vec<TileType>, ParseError> {
+    line.char_indices()
+        .map(|(i, c)| 
+            char_to_tile(c)
+                .ok_or_else(|| ParseError::at(line_number, line, &line[i..i + c.len_utf8()], "invalid character in map")))
+        .collect()
+}
+
+/// Counts the neighbours of `rows[row][column]` that have a connector facing it.
+/// The rows are still in the order they were read, top to bottom.
+fn count_connections(rows: &Vec<Vec<TileType>>, row: usize, column: usize) -> usize {
+    let north = row > 0 && rows[row - 1][column].has_south_connector();
+    let east = column + 1 < rows[row].len() && rows[row][column + 1].has_west_connector();
+    let south = row + 1 < rows.len() && rows[row + 1][column].has_north_connector();
+    let west = column > 0 && rows[row][column - 1].has_east_connector();
+
+    [north, east, south, west].iter()
+        .filter(|connects| **connects)
+        .count()
 }
 
 fn invert_x_and_y_indices(grid: Vec<Vec<TileType>>) -> Vec<Vec<TileType>>{
@@ -61,17 +106,17 @@ fn invert_x_and_y_indices(grid: Vec<Vec<TileType>>) -> Vec<Vec<TileType>>{
     corrected_grid
 }
 
-fn char_to_tile(c: char) -> TileType {
+fn char_to_tile(c: char) -> Option<TileType> {
     match c {
-        'S' => TileType::Start,
-        '|' => TileType::N_S,
-        'L' => TileType::N_E,
-        'J' => TileType::N_W,
-        'F' => TileType::E_S,
-        '-' => TileType::E_W,
-        '7' => TileType::S_W,
-        '.' => TileType::Ground,
-        _ => panic!("Invalid character found in map: {}", c)
+        'S' => Some(TileType::Start),
+        '|' => Some(TileType::N_S),
+        'L' => Some(TileType::N_E),
+        'J' => Some(TileType::N_W),
+        'F' => Some(TileType::E_S),
+        '-' => Some(TileType::E_W),
+        '7' => Some(TileType::S_W),
+        '.' => Some(TileType::Ground),
+        _ => None
     }
 }
 
@@ -86,7 +131,7 @@ mod tests {
     fn parses_sample_input() {
         let sample_input = include_str!("../sample_input.txt");
 
-        let result = parse_input(sample_input);
+        let result = parse_input(sample_input).unwrap();
 
         // .
         // .
@@ -122,4 +167,44 @@ mod tests {
         // Vector is accessed as (x, y)
         assert_eq!(result[0][2], Start);
     }
+
+    #[test]
+    fn reports_invalid_characters() {
+        let error = parse_input("..F7.\n.FJ|.\nSJ.X7\n").unwrap_err();
+
+        assert_eq!(error, ParseError {
+            line: 3,
+            column: 4,
+            text: String::from("X"),
+            message: String::from("invalid character in map"),
+        });
+    }
+
+    #[test]
+    fn reports_ragged_rows() {
+        let error = parse_input("..F7.\n.FJ|\nSJ.L7\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected a row 5 tiles wide");
+    }
+
+    #[test]
+    fn reports_missing_start() {
+        let error = parse_input("..F7.\n.FJ|.\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.message, "expected a start tile `S`");
+    }
+
+    #[test]
+    fn reports_start_without_two_connections() {
+        let error = parse_input(".S.\n...\n").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 2, "S"));
+        assert_eq!(error.message, "expected the start tile to connect to exactly two pipes");
+
+        let error = parse_input(".|.\n-S-\n...\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 2));
+    }
 }
@@ -0,0 +1,293 @@
+//! Enumerating the concrete arrangements of a record, rather than counting them.
+
+use num_bigint::{BigUint, RandBigInt};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::nodes::{Record, Spring};
+use crate::{arrangement_count_table, place_segment};
+
+/// Returns every valid arrangement of `record`'s springs, written as strings of
+/// `#` and `.` the length of its mask, in lexicographic order (`#` before `.`).
+///
+/// Arrangements are built one at a time as the iterator is advanced. Branches
+/// with no valid arrangements are never explored, so each one costs time
+/// proportional to the mask length, however many there are in total.
+pub fn arrangements(record: &Record) -> Arrangements {
+    let mask = record.springs().to_vec();
+    let damaged_segments = record.damaged_segments().to_vec();
+
+    let is_possible = arrangement_count_table::<bool>(&mask, &damaged_segments)
+        .expect("Counting with bool never overflows");
+
+    let stack = if is_possible[0][0] {
+        vec![Frame { i: 0, j: 0, next: Next::Damaged }]
+    } else {
+        vec![]
+    };
+
+    Arrangements {
+        mask,
+        damaged_segments,
+        is_possible,
+        stack,
+        arrangement: vec![],
+    }
+}
+
+/// Iterator returned by [`arrangements`].
+pub struct Arrangements {
+    mask: Vec<Spring>,
+    damaged_segments: Vec<usize>,
+    /// `is_possible[i][j]` is whether `damaged_segments[j..]` can be placed in `mask[i..]`.
+    is_possible: Vec<Vec<bool>>,
+    /// The choices made so far; the last frame is the next one to try.
+    stack: Vec<Frame>,
+    /// The springs placed so far.
+    arrangement: Vec<Spring>,
+}
+
+/// Springs up to `i` have been placed, along with segments up to `j`.
+struct Frame {
+    i: usize,
+    j: usize,
+    next: Next,
+}
+
+/// Which spring to try at a frame's `i` next: a damaged one (starting segment
+/// `j`), then a working one, then neither.
+enum Next {
+    Damaged,
+    Working,
+    Done,
+}
+
+impl Iterator for Arrangements {
+    type Item = String;
+
+    fn next(self: &mut Self) -> Option<String> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let (i, j) = (frame.i, frame.j);
+
+            if i == self.mask.len() {
+                self.stack.pop();
+                return Some(self.arrangement[..i].iter().map(Spring::to_char).collect());
+            }
+
+            match frame.next {
+                Next::Damaged => {
+                    frame.next = Next::Working;
+
+                    if j == self.damaged_segments.len() {
+                        continue;
+                    }
+
+                    let length = self.damaged_segments[j];
+                    if let Some(next) = place_segment(&self.mask, i, length) {
+                        if self.is_possible[next][j + 1] {
+                            self.arrangement.truncate(i);
+                            self.arrangement.extend(std::iter::repeat(Spring::Damaged).take(length));
+                            self.arrangement.resize(next, Spring::Operational);
+                            self.stack.push(Frame { i: next, j: j + 1, next: Next::Damaged });
+                        }
+                    }
+                }
+                Next::Working => {
+                    frame.next = Next::Done;
+
+                    if self.mask[i] != Spring::Damaged && self.is_possible[i + 1][j] {
+                        self.arrangement.truncate(i);
+                        self.arrangement.push(Spring::Operational);
+                        self.stack.push(Frame { i: i + 1, j, next: Next::Damaged });
+                    }
+                }
+                Next::Done => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the arrangement [`arrangements`] would yield at index `k`, or
+/// `None` if there are no more than `k` arrangements. Skips whole branches at a
+/// time by their counts, so never builds the arrangements before it.
+pub fn nth_arrangement(record: &Record, k: &BigUint) -> Option<String> {
+    let arrangement_counts = arrangement_count_table::<BigUint>(record.springs(), record.damaged_segments())
+        .expect("Counting with BigUint never overflows");
+
+    nth_arrangement_with_table(record, &arrangement_counts, k)
+}
+
+/// [`nth_arrangement`], given the record's [`arrangement_count_table`] so
+/// callers that already have it do not build it again.
+fn nth_arrangement_with_table(record: &Record, arrangement_counts: &Vec<Vec<BigUint>>, k: &BigUint) -> Option<String> {
+    let mask = record.springs();
+    let damaged_segments = record.damaged_segments();
+
+    let mut k = k.clone();
+    if k >= arrangement_counts[0][0] {
+        return None;
+    }
+
+    let mut arrangement = Vec::with_capacity(mask.len());
+    let (mut i, mut j) = (0, 0);
+    while i < mask.len() {
+        // Arrangements starting segment j at i come first
+        if j < damaged_segments.len() {
+            if let Some(next) = place_segment(mask, i, damaged_segments[j]) {
+                let count = &arrangement_counts[next][j + 1];
+                if k < *count {
+                    arrangement.extend(std::iter::repeat(Spring::Damaged).take(damaged_segments[j]));
+                    arrangement.resize(next, Spring::Operational);
+                    (i, j) = (next, j + 1);
+                    continue;
+                }
+                k -= count;
+            }
+        }
+
+        // Otherwise k is among those with a working spring at i
+        arrangement.push(Spring::Operational);
+        i += 1;
+    }
+
+    Some(arrangement.iter().map(Spring::to_char).collect())
+}
+
+/// Draws one of `record`'s arrangements, each with exactly the same
+/// probability, or `None` if it has none. The same `seed` always draws the
+/// same arrangement.
+pub fn sample_arrangement(record: &Record, seed: u64) -> Option<String> {
+    let arrangement_counts = arrangement_count_table::<BigUint>(record.springs(), record.damaged_segments())
+        .expect("Counting with BigUint never overflows");
+    let total = &arrangement_counts[0][0];
+    if *total == BigUint::ZERO {
+        return None;
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let k = rng.gen_biguint_below(total);
+
+    nth_arrangement_with_table(record, &arrangement_counts, &k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_input;
+    use crate::tests::springs;
+    use crate::count_valid_arrangements;
+
+    fn matches(mask: &[Spring], arrangement: &[Spring]) -> bool {
+        mask.len() == arrangement.len()
+            && mask.iter().zip(arrangement).all(|(mask_spring, spring)| *mask_spring == Spring::Unknown || mask_spring == spring)
+    }
+
+    #[test]
+    fn arrangements_lists_every_arrangement_in_order() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+
+        let arrangements = arrangements(&record).collect::<Vec<String>>();
+
+        assert_eq!(arrangements, vec![
+            ".###.##.#...",
+            ".###.##..#..",
+            ".###.##...#.",
+            ".###.##....#",
+            ".###..##.#..",
+            ".###..##..#.",
+            ".###..##...#",
+            ".###...##.#.",
+            ".###...##..#",
+            ".###....##.#",
+        ]);
+    }
+
+    #[test]
+    fn arrangements_agrees_with_count_on_sample_records() {
+        let records = parse_input(include_str!("../sample_input.txt")).unwrap();
+
+        for record in &records {
+            let arrangements = arrangements(record).collect::<Vec<String>>();
+
+            assert_eq!(arrangements.len(), count_valid_arrangements(record), "{}", record);
+            assert!(arrangements.windows(2).all(|pair| pair[0] < pair[1]));
+            for arrangement in &arrangements {
+                assert_eq!(arrangement.len(), record.springs().len());
+                assert!(matches(record.springs(), &springs(arrangement)), "{} {}", record, arrangement);
+            }
+        }
+    }
+
+    #[test]
+    fn arrangements_is_empty_when_nothing_fits() {
+        assert_eq!(arrangements(&"#.# 3".parse::<Record>().unwrap()).count(), 0);
+        assert_eq!(arrangements(&Record::new(vec![], vec![]).unwrap()).collect::<Vec<String>>(), vec![""]);
+    }
+
+    #[test]
+    fn arrangements_is_lazy() {
+        // About 7 * 10^46 arrangements
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap().unfold(40);
+
+        let first = arrangements(&record).next().unwrap();
+
+        assert!(first.starts_with(".###.##.#.."));
+        assert!(matches(record.springs(), &springs(&first)));
+    }
+
+    #[test]
+    fn nth_arrangement_agrees_with_arrangements() {
+        let records = parse_input(include_str!("../sample_input.txt")).unwrap();
+
+        for record in &records {
+            let arrangements = arrangements(record).collect::<Vec<String>>();
+
+            for (k, arrangement) in arrangements.iter().enumerate() {
+                assert_eq!(nth_arrangement(record, &BigUint::from(k)).as_ref(), Some(arrangement));
+            }
+            assert_eq!(nth_arrangement(record, &BigUint::from(arrangements.len())), None);
+        }
+    }
+
+    #[test]
+    fn nth_arrangement_of_huge_record() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap().unfold(40);
+        let total = "73715548806266747615154599770903587341308593750".parse::<BigUint>().unwrap();
+
+        let first = nth_arrangement(&record, &BigUint::ZERO).unwrap();
+        assert_eq!(Some(first), arrangements(&record).next());
+
+        let last = nth_arrangement(&record, &(&total - 1u8)).unwrap();
+        assert!(last.ends_with("....##.#"));
+        assert!(matches(record.springs(), &springs(&last)));
+
+        assert_eq!(nth_arrangement(&record, &total), None);
+    }
+
+    #[test]
+    fn sample_arrangement_is_uniform() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+        let arrangements = arrangements(&record).collect::<Vec<String>>();
+
+        let mut draws = vec![0; arrangements.len()];
+        for seed in 0..10_000 {
+            let arrangement = sample_arrangement(&record, seed).unwrap();
+            let index = arrangements.iter().position(|a| *a == arrangement).unwrap();
+            draws[index] += 1;
+        }
+
+        // Each of the 10 arrangements is expected 1000 times, with a standard deviation of 30
+        assert!(draws.iter().all(|count| (850..1150).contains(count)), "{:?}", draws);
+    }
+
+    #[test]
+    fn sample_arrangement_depends_only_on_seed() {
+        let record = ".??..??...?##. 1,1,3".parse::<Record>().unwrap();
+
+        assert_eq!(sample_arrangement(&record, 12), sample_arrangement(&record, 12));
+        assert_eq!(sample_arrangement(&"#.# 3".parse::<Record>().unwrap(), 12), None);
+    }
+}
@@ -0,0 +1,22 @@
+use day_12::explain::explain;
+use day_12::nodes::Record;
+
+/// Prints the table behind one record's arrangement count, e.g.
+/// `explain-record "?###???????? 3,2,1"`, or with `--json` as JSON to diff.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let json = args.iter().any(|arg| arg == "--json");
+    let record = args.iter()
+        .find(|arg| *arg != "--json")
+        .expect("Usage: explain-record <record> [--json]")
+        .parse::<Record>()
+        .unwrap_or_else(|e| panic!("Could not parse record: {}", e));
+
+    let explanation = explain(&record).expect("Could not count arrangements");
+
+    if json {
+        println!("{}", explanation.to_json());
+    } else {
+        print!("{}", explanation);
+    }
+}
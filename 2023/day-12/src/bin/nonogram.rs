@@ -0,0 +1,16 @@
+use aoc_core::input::read_arg;
+use day_12::nonogram::{parse_puzzle, Solutions};
+
+/// Solves the nonogram in the file given as the first argument (or on stdin,
+/// given `-`) and prints the solution.
+fn main() {
+    let arg = std::env::args().nth(1).expect("Usage: nonogram <puzzle file | ->");
+    let input = read_arg(&arg).expect("Could not read puzzle");
+    let puzzle = parse_puzzle(&input).expect("Could not parse puzzle");
+
+    match puzzle.solve() {
+        Solutions::None => println!("No solution"),
+        Solutions::Unique(grid) => print!("Unique solution:\n{}", grid),
+        Solutions::Multiple(first, second) => print!("More than one solution, including:\n{}\nand:\n{}", first, second),
+    }
+}
@@ -0,0 +1,24 @@
+use std::cmp::Reverse;
+
+use aoc_core::input::InputSource;
+use aoc_core::Solution;
+use day_12::{time_valid_arrangement_counts, Day12};
+
+/// Lists the records in the puzzle input that take longest to count, slowest
+/// first. The second argument is how many to list (10 by default).
+fn main() {
+    let input = InputSource::from_args()
+        .read(Day12::YEAR, Day12::DAY)
+        .expect("Could not read puzzle input");
+    let records = Day12::parse(&input).expect("Could not parse puzzle input");
+    let slowest_count = std::env::args().nth(2)
+        .map(|arg| arg.parse::<usize>().expect("Expected the number of records to list"))
+        .unwrap_or(10);
+
+    let mut timings = time_valid_arrangement_counts(&records);
+    timings.sort_by_key(|timing| Reverse(timing.elapsed));
+
+    for timing in timings.iter().take(slowest_count) {
+        println!("{:>12?}  {:>6} arrangements  {}", timing.elapsed, timing.count, records[timing.index]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Integer types that arrangement counts can be computed in.
+//!
+//! Unfolding a record more than the puzzle's five times quickly produces
+//! counts that no longer fit in a `u64`, so the counting functions are generic
+//! over [`Count`] and report an [`Overflow`] instead of wrapping.
+
+use std::any::type_name;
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+
+pub trait Count: Clone + Display + Send {
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    /// Returns `None` if the sum does not fit.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_count_for_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_count_for_primitive!(usize, u64, u128);
+
+impl Count for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn one() -> Self {
+        BigUint::from(1u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+/// Only whether there are any arrangements at all; addition is `or`.
+impl Count for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self || *other)
+    }
+}
+
+/// A count modulo the prime `P`, for when only the residue is needed. Never overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModPrime<const P: u64>(pub u64);
+
+impl<const P: u64> Display for ModPrime<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Count for ModPrime<P> {
+    fn zero() -> Self {
+        ModPrime(0)
+    }
+
+    fn one() -> Self {
+        ModPrime(1 % P)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(ModPrime(((self.0 as u128 + other.0 as u128) % P as u128) as u64))
+    }
+}
+
+/// An arrangement count did not fit in the [`Count`] it was computed in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub count_type: &'static str,
+}
+
+impl Overflow {
+    pub fn of<C: Count>() -> Overflow {
+        Overflow { count_type: type_name::<C>() }
+    }
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "arrangement count overflowed {}", self.count_type)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Adds `other` to `count`, or reports which type overflowed.
+pub(crate) fn add<C: Count>(count: &C, other: &C) -> Result<C, Overflow> {
+    count.checked_add(other).ok_or_else(Overflow::of::<C>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_counts_report_overflow() {
+        assert_eq!(add(&u64::MAX, &0), Ok(u64::MAX));
+        assert_eq!(add(&u64::MAX, &1), Err(Overflow::of::<u64>()));
+        assert_eq!(Overflow::of::<u64>().to_string(), "arrangement count overflowed u64");
+    }
+
+    #[test]
+    fn mod_prime_counts_wrap_at_prime() {
+        let almost = ModPrime::<7>(6);
+
+        assert_eq!(add(&almost, &ModPrime::one()), Ok(ModPrime(0)));
+        assert_eq!(ModPrime::<1>::one(), ModPrime(0));
+    }
+}
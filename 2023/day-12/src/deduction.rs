@@ -0,0 +1,146 @@
+//! Which springs of a record are settled by its damaged segments alone.
+
+use num_bigint::BigUint;
+
+use crate::nodes::{Record, Spring};
+use crate::{arrangement_count_table, place_segment};
+
+/// What the valid arrangements of a record say about one of its springs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    /// Damaged in every arrangement.
+    Damaged,
+    /// Working in every arrangement.
+    Working,
+    /// Damaged in some arrangements and working in the rest.
+    Ambiguous { damaged: BigUint, working: BigUint },
+}
+
+/// Works out, for each spring in `record`'s mask, whether it is forced to be
+/// damaged or working, or in how many arrangements it is each. Returns `None`
+/// if the record has no valid arrangements.
+///
+/// Rather than enumerating arrangements, every way of placing each segment is
+/// weighted by the number of ways to place the segments before it (counted on
+/// the reversed record) times the number of ways to place those after it.
+pub fn deduce_cells(record: &Record) -> Option<Vec<Cell>> {
+    let mask = record.springs();
+    let damaged_segments = record.damaged_segments();
+    let (n, m) = (mask.len(), damaged_segments.len());
+
+    // suffix_counts[i][j]: ways to place damaged_segments[j..] in mask[i..]
+    let suffix_counts = arrangement_count_table::<BigUint>(mask, damaged_segments)
+        .expect("Counting with BigUint never overflows");
+    let total = suffix_counts[0][0].clone();
+    if total == BigUint::ZERO {
+        return None;
+    }
+
+    // reversed_counts[n - i][m - j]: ways to place damaged_segments[..j] in mask[..i]
+    let reversed_mask = mask.iter().rev().copied().collect::<Vec<Spring>>();
+    let reversed_segments = damaged_segments.iter().rev().copied().collect::<Vec<usize>>();
+    let reversed_counts = arrangement_count_table::<BigUint>(&reversed_mask, &reversed_segments)
+        .expect("Counting with BigUint never overflows");
+
+    // Arrangements with segment j at start, for every start it fits at, count
+    // towards each of its springs being damaged: they are added where the
+    // segment starts and taken away again where it ends.
+    let mut segments_starting = vec![BigUint::ZERO; n + 1];
+    let mut segments_ending = vec![BigUint::ZERO; n + 1];
+    for j in 0..m {
+        for start in 0..n {
+            let Some(next) = place_segment(mask, start, damaged_segments[j]) else {
+                continue;
+            };
+
+            // The spring before the segment has to be working
+            let before = if start == 0 {
+                &reversed_counts[n][m - j]
+            } else if mask[start - 1] != Spring::Damaged {
+                &reversed_counts[n - (start - 1)][m - j]
+            } else {
+                continue;
+            };
+
+            let count = before * &suffix_counts[next][j + 1];
+            if count != BigUint::ZERO {
+                segments_starting[start] += &count;
+                segments_ending[start + damaged_segments[j]] += &count;
+            }
+        }
+    }
+
+    let mut cells = Vec::with_capacity(n);
+    let mut damaged = BigUint::ZERO;
+    for i in 0..n {
+        damaged -= &segments_ending[i];
+        damaged += &segments_starting[i];
+
+        let cell = if damaged == BigUint::ZERO {
+            Cell::Working
+        } else if damaged == total {
+            Cell::Damaged
+        } else {
+            Cell::Ambiguous { damaged: damaged.clone(), working: &total - &damaged }
+        };
+        cells.push(cell);
+    }
+
+    Some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrangements::arrangements;
+    use crate::parser::parse_input;
+
+    fn ambiguous(damaged: u32, working: u32) -> Cell {
+        Cell::Ambiguous { damaged: BigUint::from(damaged), working: BigUint::from(working) }
+    }
+
+    #[test]
+    fn deduce_cells_finds_forced_springs() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+
+        let cells = deduce_cells(&record).unwrap();
+
+        assert_eq!(cells[..5], [Cell::Working, Cell::Damaged, Cell::Damaged, Cell::Damaged, Cell::Working]);
+        assert_eq!(cells[5..], [
+            ambiguous(4, 6),
+            ambiguous(7, 3),
+            ambiguous(5, 5),
+            ambiguous(4, 6),
+            ambiguous(3, 7),
+            ambiguous(3, 7),
+            ambiguous(4, 6),
+        ]);
+    }
+
+    #[test]
+    fn deduce_cells_agrees_with_arrangements_on_sample_records() {
+        let records = parse_input(include_str!("../sample_input.txt")).unwrap();
+
+        for record in &records {
+            let arrangements = arrangements(record).collect::<Vec<String>>();
+            let cells = deduce_cells(record).unwrap();
+
+            for (i, cell) in cells.iter().enumerate() {
+                let damaged = arrangements.iter().filter(|a| a.as_bytes()[i] == b'#').count() as u32;
+                let expected = match damaged {
+                    0 => Cell::Working,
+                    d if d as usize == arrangements.len() => Cell::Damaged,
+                    d => ambiguous(d, arrangements.len() as u32 - d),
+                };
+                assert_eq!(*cell, expected, "{} at {}", record, i);
+            }
+        }
+    }
+
+    #[test]
+    fn deduce_cells_of_impossible_record() {
+        assert_eq!(deduce_cells(&"#.# 3".parse::<Record>().unwrap()), None);
+        let record = Record::new(vec![Spring::Unknown; 3], vec![]).unwrap();
+        assert_eq!(deduce_cells(&record), Some(vec![Cell::Working; 3]));
+    }
+}
@@ -0,0 +1,162 @@
+//! Showing the work behind a record's arrangement count, for tracking down
+//! where a count goes wrong.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::checked_arrangement_count_table;
+use crate::count::Overflow;
+use crate::nodes::Record;
+
+/// The table the memoized count of one record is built from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// The record, as written in the puzzle input.
+    pub record: String,
+    pub arrangements: u128,
+    /// `table[j][i]` is the number of ways to place the damaged segments from
+    /// the `j`th on in the springs from the `i`th on. The last row is for
+    /// having placed every segment, and the last column for having reached
+    /// the end of the springs. Counts too large for a `u128` are `None`; the
+    /// record's own count never depends on them.
+    pub table: Vec<Vec<Option<u128>>>,
+}
+
+pub fn explain(record: &Record) -> Result<Explanation, Overflow> {
+    let arrangement_counts = checked_arrangement_count_table::<u128>(record.springs(), record.damaged_segments());
+    let arrangements = arrangement_counts[0][0].ok_or_else(Overflow::of::<u128>)?;
+
+    // The count table is indexed by position first; group by segment instead
+    let table = (0..record.damaged_segments().len() + 1)
+        .map(|j| arrangement_counts.iter().map(|counts| counts[j]).collect())
+        .collect();
+
+    Ok(Explanation {
+        record: record.to_string(),
+        arrangements,
+        table,
+    })
+}
+
+impl Explanation {
+    pub fn to_json(self: &Self) -> String {
+        serde_json::to_string_pretty(self).expect("Explanations are always valid JSON")
+    }
+}
+
+/// Lays the table out with the springs along the top and one row per damaged
+/// segment, e.g. `#2` for the row placing segments from one of length 2 on.
+/// Counts too large to compute are shown as `-`.
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (springs, damaged_segments) = self.record.split_once(' ').unwrap_or((&self.record, ""));
+        let damaged_segments = damaged_segments.split(',').filter(|segment| !segment.is_empty());
+
+        let labels = damaged_segments
+            .map(|segment| format!("#{}", segment))
+            .chain(std::iter::once(String::from("done")))
+            .collect::<Vec<String>>();
+        let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+        let counts = self.table.iter()
+            .map(|counts| counts.iter()
+                .map(|count| count.map_or_else(|| String::from("-"), |count| count.to_string()))
+                .collect::<Vec<String>>())
+            .collect::<Vec<Vec<String>>>();
+        let width = counts.iter()
+            .flatten()
+            .map(|count| count.len())
+            .max()
+            .unwrap_or(1);
+
+        writeln!(f, "{}: {} arrangements", self.record, self.arrangements)?;
+
+        // The last column, past the end of the springs, is marked `|`
+        write!(f, "{:label_width$}", "")?;
+        for spring in springs.chars().chain(std::iter::once('|')) {
+            write!(f, " {:>width$}", spring)?;
+        }
+        writeln!(f)?;
+
+        for (label, counts) in labels.iter().zip(&counts) {
+            write!(f, "{:label_width$}", label)?;
+            for count in counts {
+                write!(f, " {:>width$}", count)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_builds_table_by_segment_and_position() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+
+        let explanation = explain(&record).unwrap();
+
+        assert_eq!(explanation.arrangements, 10);
+        assert_eq!(explanation.table.len(), 4);
+        assert!(explanation.table.iter().all(|counts| counts.len() == 13));
+        assert_eq!(explanation.table[0][0], Some(10));
+        assert_eq!(explanation.table[3][12], Some(1));
+    }
+
+    #[test]
+    fn explain_leaves_out_counts_too_large_to_matter() {
+        let record = format!("#.{} {}", "?".repeat(189), vec!["1"; 52].join(","))
+            .parse::<Record>()
+            .unwrap();
+
+        let explanation = explain(&record).unwrap();
+
+        assert_eq!(explanation.arrangements, 334220920135799419191431069990960889240);
+        assert_eq!(explanation.table[0][2], None);
+        assert!(explanation.to_string().contains(" - "));
+    }
+
+    #[test]
+    fn explanation_prints_table() {
+        let record = "???.### 1,1,3".parse::<Record>().unwrap();
+
+        let explanation = explain(&record).unwrap();
+
+        assert_eq!(explanation.to_string(), "\
+???.### 1,1,3: 1 arrangements
+     ? ? ? . # # # |
+#1   1 0 0 0 0 0 0 0
+#1   3 2 1 0 0 0 0 0
+#3   1 1 1 1 1 0 0 0
+done 0 0 0 0 0 0 0 1
+");
+    }
+
+    #[test]
+    fn explanation_as_json() {
+        let record = "#? 1".parse::<Record>().unwrap();
+
+        let explanation = explain(&record).unwrap();
+
+        assert_eq!(explanation.to_json(), r##"{
+  "record": "#? 1",
+  "arrangements": 1,
+  "table": [
+    [
+      1,
+      1,
+      0
+    ],
+    [
+      0,
+      1,
+      1
+    ]
+  ]
+}"##);
+    }
+}
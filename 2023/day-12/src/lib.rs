@@ -0,0 +1,451 @@
+pub mod arrangements;
+pub mod count;
+pub mod deduction;
+pub mod explain;
+pub mod nonogram;
+pub mod nodes;
+mod parser;
+
+use std::time::{Duration, Instant};
+
+use aoc_core::{ParseError, Solution};
+
+use crate::count::{Count, Overflow};
+use crate::nodes::{Record, Spring};
+use crate::parser::parse_input;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
+
+    type Parsed<'a> = Vec<Record>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(records: &Vec<Record>) -> usize {
+        sum_valid_arrangement_counts(records)
+    }
+
+    fn part_two(records: &Vec<Record>) -> Option<usize> {
+        let sum = sum_unfolded_arrangement_counts::<usize>(records, UNFOLD_FACTOR)
+            .expect("Arrangement counts of the puzzle input fit in a usize");
+
+        Some(sum)
+    }
+}
+
+/// How many copies of each record the unfolded records of part two are made of.
+pub const UNFOLD_FACTOR: usize = 5;
+
+fn sum_valid_arrangement_counts(records: &Vec<Record>) -> usize {
+    map_records(records, count_valid_arrangements)
+        .iter()
+        .sum()
+}
+
+/// How long counting one record's arrangements took, for finding the records
+/// that are slow to count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordTiming {
+    /// Where the record is in the list of records.
+    pub index: usize,
+    pub count: usize,
+    pub elapsed: Duration,
+}
+
+/// Counts each record's arrangements as part one does, timing each record.
+/// Timings are in the order of `records`.
+pub fn time_valid_arrangement_counts(records: &Vec<Record>) -> Vec<RecordTiming> {
+    let indexed_records = records.iter().enumerate().collect::<Vec<(usize, &Record)>>();
+
+    map_records(&indexed_records, |(index, record)| {
+        let start = Instant::now();
+        let count = count_valid_arrangements(record);
+
+        RecordTiming { index: *index, count, elapsed: start.elapsed() }
+    })
+}
+
+/// Applies `f` to every record, spread across every core when the `parallel`
+/// feature is on. Results are in the order of `records` either way, so sums
+/// of them come out the same.
+#[cfg(feature = "parallel")]
+fn map_records<R: Sync, T: Send>(records: &[R], f: impl Fn(&R) -> T + Sync + Send) -> Vec<T> {
+    use rayon::prelude::*;
+
+    records.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn map_records<R, T>(records: &[R], f: impl Fn(&R) -> T) -> Vec<T> {
+    records.iter().map(f).collect()
+}
+
+/// Sums [`count_unfolded_arrangements`] over every record.
+pub fn sum_unfolded_arrangement_counts<C: Count>(
+    records: &Vec<Record>, 
+    unfold_factor: usize) 
+    -> Result<C, Overflow> 
+{
+    let counts = map_records(records, |record| count_unfolded_arrangements::<C>(record, unfold_factor));
+
+    let mut sum = C::zero();
+    for count in counts {
+        sum = count::add(&sum, &count?)?;
+    }
+
+    Ok(sum)
+}
+
+/// Counts the arrangements of `record` once it has been unfolded `unfold_factor`
+/// times, computed in `C`.
+pub fn count_unfolded_arrangements<C: Count>(
+    record: &Record, 
+    unfold_factor: usize) 
+    -> Result<C, Overflow> 
+{
+    count_valid_arrangements_memoized(&record.unfold(unfold_factor))
+}
+
+/// Counts the same arrangements as [`count_valid_arrangements`], but without
+/// building them: each (position in the mask, segments left to place) pair is
+/// only counted once, which keeps unfolded records tractable.
+fn count_valid_arrangements_memoized<C: Count>(record: &Record) -> Result<C, Overflow> {
+    let arrangement_counts = checked_arrangement_count_table::<C>(record.springs(), record.damaged_segments());
+
+    arrangement_counts[0][0].clone().ok_or_else(Overflow::of::<C>)
+}
+
+/// Builds the table behind [`count_valid_arrangements_memoized`]:
+/// `arrangement_counts[i][j]` is the number of ways to place `damaged_segments[j..]`
+/// in `mask[i..]`. Past the end of the mask, only placing nothing works.
+///
+/// A cell is `None` if its count does not fit in `C`, and so is every cell
+/// counted from it. Cells the record's own count `[0][0]` never reaches can be
+/// far larger than it, so only a `None` there means the count overflowed.
+pub(crate) fn checked_arrangement_count_table<C: Count>(mask: &[Spring], damaged_segments: &[usize]) -> Vec<Vec<Option<C>>> {
+    let mut arrangement_counts = vec![vec![Some(C::zero()); damaged_segments.len() + 1]; mask.len() + 1];
+    arrangement_counts[mask.len()][damaged_segments.len()] = Some(C::one());
+
+    let add = |count: Option<C>, other: &Option<C>| count?.checked_add(other.as_ref()?);
+
+    for i in (0..mask.len()).rev() {
+        for j in (0..damaged_segments.len() + 1).rev() {
+            let mut count = Some(C::zero());
+
+            // A working spring at i
+            if mask[i] != Spring::Damaged {
+                count = add(count, &arrangement_counts[i + 1][j]);
+            }
+
+            // Or segment j starts at i
+            if j < damaged_segments.len() {
+                if let Some(next) = place_segment(mask, i, damaged_segments[j]) {
+                    count = add(count, &arrangement_counts[next][j + 1]);
+                }
+            }
+
+            arrangement_counts[i][j] = count;
+        }
+    }
+
+    arrangement_counts
+}
+
+/// [`checked_arrangement_count_table`] for count types that cannot overflow,
+/// such as `BigUint` and `bool`, whose every cell is known. Fails if any cell
+/// at all does not fit, so it is no use for telling whether a count fits.
+pub(crate) fn arrangement_count_table<C: Count>(mask: &[Spring], damaged_segments: &[usize]) -> Result<Vec<Vec<C>>, Overflow> {
+    checked_arrangement_count_table::<C>(mask, damaged_segments)
+        .into_iter()
+        .map(|counts| counts.into_iter().collect::<Option<Vec<C>>>())
+        .collect::<Option<Vec<Vec<C>>>>()
+        .ok_or_else(Overflow::of::<C>)
+}
+
+/// If a segment of `length` damaged springs can start at `i`, returns where
+/// the rest of the record starts: after the segment and the working spring
+/// that has to follow it (unless the segment ends the mask).
+pub(crate) fn place_segment(mask: &[Spring], i: usize, length: usize) -> Option<usize> {
+    let end = i + length;
+
+    let fits = end <= mask.len()
+        && mask[i..end].iter().all(|spring| *spring != Spring::Operational)
+        && (end == mask.len() || mask[end] != Spring::Damaged);
+
+    if fits {
+        Some((end + 1).min(mask.len()))
+    } else {
+        None
+    }
+}
+
+fn count_valid_arrangements(record: &Record) -> usize {
+    let mask = record.springs();
+    let damaged_segments = record.damaged_segments();
+
+    let num_of_segment_gaps = damaged_segments.len().saturating_sub(1);
+    let num_of_damaged_springs:usize = damaged_segments.iter().sum();
+    let Some(num_of_unassigned_working_springs) = mask.len()
+        .checked_sub(num_of_segment_gaps) // Each segment gap must have at least one working spring
+        .and_then(|remaining| remaining.checked_sub(num_of_damaged_springs))
+    else {
+        // The segments do not fit in the mask at all
+        return 0;
+    };
+
+    count_valid_arrangements_recursive(
+        mask, 
+        0, 
+        num_of_unassigned_working_springs,
+        damaged_segments, 
+        0
+    )
+}
+
+/// Places `damaged_segments[depth..]` in `mask[position..]`, with
+/// `num_of_unassigned_working_springs` working springs to spare beyond the
+/// one each gap between segments needs. Only the springs placed at each step
+/// are checked against the mask; everything before `position` already matches.
+fn count_valid_arrangements_recursive(
+    mask: &[Spring], 
+    position: usize, 
+    num_of_unassigned_working_springs: usize,
+    damaged_segments: &[usize], 
+    depth: usize) 
+    -> usize 
+{
+    if depth == damaged_segments.len() {
+        return if can_all_be(&mask[position..], Spring::Operational) {
+            1
+        } else {
+            0
+        }
+    }
+
+    let mut position = position;
+    if depth > 0 {
+        if !can_be(mask[position], Spring::Operational) {
+            return 0;
+        }
+        position += 1;
+    }
+
+    let segment = damaged_segments[depth];
+
+    let mut valid_arrangement_count = 0;
+    for i in 0..(num_of_unassigned_working_springs + 1) {
+        // Each step adds one more working spring before the segment; once
+        // one of them can't be working, neither can any longer run.
+        if i > 0 && !can_be(mask[position + i - 1], Spring::Operational) {
+            break;
+        }
+
+        let segment_start = position + i;
+        if !can_all_be(&mask[segment_start..segment_start + segment], Spring::Damaged) {
+            continue;
+        }
+
+        valid_arrangement_count += count_valid_arrangements_recursive(
+            mask, 
+            segment_start + segment, 
+            num_of_unassigned_working_springs - i, 
+            damaged_segments, 
+            depth + 1
+        );
+    }
+
+    valid_arrangement_count
+}
+
+fn can_be(mask_spring: Spring, spring: Spring) -> bool {
+    mask_spring == Spring::Unknown || mask_spring == spring
+}
+
+fn can_all_be(mask: &[Spring], spring: Spring) -> bool {
+    mask.iter().all(|mask_spring| can_be(*mask_spring, spring))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::count::ModPrime;
+
+    pub(crate) fn springs(springs: &str) -> Vec<Spring> {
+        springs.chars().map(|c| Spring::from_char(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn count_valid_arrangements_returns_1() {
+        let record = "???.### 1,1,3".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+    }
+
+    #[test]
+    fn count_valid_arrangements_returns_0_when_segments_exactly_fill_an_impossible_mask() {
+        // The segments take up the whole mask, so there is one candidate
+        // arrangement, `####.#.#`, and the mask's last spring rules it out
+        let record = "????.#.. 4,1,1".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 0);
+    }
+
+    #[test]
+    fn count_valid_arrangements_returns_number_of_gaps() {
+        let record = "?#.??????#??#?#?#?#? 1,1,15".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+        
+        let record = "??????##????#?.?.??. 1,7,4,1,2".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+    }
+
+    #[test]
+    fn count_valid_arrangements_works_on_sample_records() {
+        /*        
+            ???.### 1,1,3 - 1 arrangement
+            .??..??...?##. 1,1,3 - 4 arrangements
+            ?#?#?#?#?#?#?#? 1,3,1,6 - 1 arrangement
+            ????.#...#... 4,1,1 - 1 arrangement
+            ????.######..#####. 1,6,5 - 4 arrangements
+            ?###???????? 3,2,1 - 10 arrangements
+        */
+
+        let record = "???.### 1,1,3".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+
+        let record = ".??..??...?##. 1,1,3".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 4);
+
+        let record = "?#?#?#?#?#?#?#? 1,3,1,6".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+
+        let record = "????.#...#... 4,1,1".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 1);
+
+        let record = "????.######..#####. 1,6,5".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 4);
+
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+        let arrangement_count = count_valid_arrangements(&record);
+        assert_eq!(arrangement_count, 10);
+    }
+
+    #[test]
+    fn count_valid_arrangements_memoized_agrees_with_recursive() {
+        let mut records = parse_input(include_str!("../sample_input.txt")).unwrap();
+        // Records with no arrangements, and records too short for their segments
+        records.extend(["#.# 3", ". 1", "????.#.. 4,1,1", "# 2", "?? 1,1", "## 1,1", "... 1"]
+            .map(|record| record.parse::<Record>().unwrap()));
+
+        for record in &records {
+            assert_eq!(
+                count_valid_arrangements_memoized::<usize>(record), 
+                Ok(count_valid_arrangements(record)), 
+                "{}", record);
+        }
+    }
+
+    #[test]
+    fn count_valid_arrangements_memoized_works_on_unfolded_sample_records() {
+        let records = parse_input(include_str!("../sample_input.txt")).unwrap();
+        let expected_counts = [1, 16384, 1, 16, 2500, 506250];
+
+        for (record, expected_count) in records.iter().zip(expected_counts) {
+            assert_eq!(count_unfolded_arrangements::<usize>(record, UNFOLD_FACTOR), Ok(expected_count), "{}", record);
+        }
+
+        assert_eq!(Day12::part_two(&records), Some(525152));
+    }
+
+    #[test]
+    fn count_unfolded_arrangements_ignores_overflow_the_count_does_not_depend_on() {
+        // Placing every segment in the `?`s alone, which the leading `#` rules
+        // out, overflows a u64; the count itself does not
+        let record = format!("#.{} {}", "?".repeat(97), vec!["1"; 25].join(","))
+            .parse::<Record>()
+            .unwrap();
+
+        assert_eq!(count_unfolded_arrangements::<u64>(&record, 1), Ok(17529515713716297876));
+        assert_eq!(count_unfolded_arrangements::<BigUint>(&record, 1), Ok(BigUint::from(17529515713716297876u64)));
+    }
+
+    #[test]
+    fn count_unfolded_arrangements_detects_overflow() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+
+        assert_eq!(count_unfolded_arrangements::<u64>(&record, 16), Ok(4378938903808593750));
+        assert_eq!(count_unfolded_arrangements::<u64>(&record, 17), Err(Overflow::of::<u64>()));
+        assert_eq!(count_unfolded_arrangements::<u128>(&record, 17), Ok(65684083557128906250));
+        assert_eq!(count_unfolded_arrangements::<u128>(&record, 33), Err(Overflow::of::<u128>()));
+    }
+
+    #[test]
+    fn count_unfolded_arrangements_in_big_and_modular_integers() {
+        let record = "?###???????? 3,2,1".parse::<Record>().unwrap();
+
+        let count = count_unfolded_arrangements::<BigUint>(&record, 40).unwrap();
+        assert_eq!(count.to_string(), "73715548806266747615154599770903587341308593750");
+
+        let count = count_unfolded_arrangements::<ModPrime<1_000_000_007>>(&record, 40).unwrap();
+        assert_eq!(count, ModPrime(95884047));
+    }
+
+    #[test]
+    fn sum_unfolded_arrangement_counts_detects_overflow_of_sum() {
+        let records = vec!["?###???????? 3,2,1".parse::<Record>().unwrap(); 5];
+
+        assert_eq!(sum_unfolded_arrangement_counts::<u64>(&records, 16), Err(Overflow::of::<u64>()));
+        assert_eq!(sum_unfolded_arrangement_counts::<u128>(&records, 16), Ok(5 * 4378938903808593750));
+    }
+
+    #[test]
+    fn sum_valid_arrangement_counts_works_on_sample() {
+        let input = 
+"???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+        let records = parse_input(&input).unwrap();
+
+        let sum = sum_valid_arrangement_counts(&records);
+
+        assert_eq!(sum, 21);
+        assert_eq!(Day12::part_one(&records), 21);
+    }
+
+    #[test]
+    fn time_valid_arrangement_counts_keeps_record_order() {
+        let records = parse_input(include_str!("../sample_input.txt")).unwrap();
+
+        let timings = time_valid_arrangement_counts(&records);
+
+        let counts = timings.iter().map(|timing| (timing.index, timing.count)).collect::<Vec<(usize, usize)>>();
+        assert_eq!(counts, vec![(0, 1), (1, 4), (2, 1), (3, 1), (4, 4), (5, 10)]);
+    }
+
+    #[test]
+    fn solves_puzzle_input() {
+        let records = Day12::parse(include_str!("../part1.txt")).unwrap();
+
+        assert_eq!(Day12::part_one(&records), 8193);
+        assert_eq!(Day12::part_two(&records), Some(45322533163795));
+    }
+}
\ No newline at end of file
@@ -1,4 +1,114 @@
-//#[derive(Debug, Copy, Clone, PartialEq, Eq)] //PartialOrd, Ord
-enum Node {
-    WorkingSpace(&str) // will be something like "", ".", ".." 
-}
\ No newline at end of file
+//! The condition records themselves: which springs are known to be working
+//! or damaged, and the sizes of the groups the damaged ones come in.
+
+use std::fmt::Display;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Spring {
+    pub fn from_char(c: char) -> Option<Spring> {
+        match c {
+            '.' => Some(Spring::Operational),
+            '#' => Some(Spring::Damaged),
+            '?' => Some(Spring::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self: &Self) -> char {
+        match self {
+            Spring::Operational => '.',
+            Spring::Damaged => '#',
+            Spring::Unknown => '?',
+        }
+    }
+}
+
+/// One row of springs and the sizes of its contiguous groups of damaged
+/// springs, in order. Every group has at least one spring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    springs: Vec<Spring>,
+    damaged_segments: Vec<usize>,
+}
+
+/// A record was given a damaged segment of length 0, at `index` in its segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptySegment {
+    pub index: usize,
+}
+
+impl Display for EmptySegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "damaged segment {} is empty", self.index + 1)
+    }
+}
+
+impl std::error::Error for EmptySegment {}
+
+impl Record {
+    pub fn new(springs: Vec<Spring>, damaged_segments: Vec<usize>) -> Result<Record, EmptySegment> {
+        if let Some(index) = damaged_segments.iter().position(|segment| *segment == 0) {
+            return Err(EmptySegment { index });
+        }
+
+        Ok(Record { springs, damaged_segments })
+    }
+
+    pub fn springs(self: &Self) -> &[Spring] {
+        &self.springs
+    }
+
+    pub fn damaged_segments(self: &Self) -> &[usize] {
+        &self.damaged_segments
+    }
+
+    /// Repeats the springs `unfold_factor` times with an unknown spring between
+    /// each copy, and the damaged segments `unfold_factor` times.
+    pub fn unfold(self: &Self, unfold_factor: usize) -> Record {
+        let springs = vec![self.springs.clone(); unfold_factor].join(&Spring::Unknown);
+        let damaged_segments = self.damaged_segments.repeat(unfold_factor);
+
+        Record { springs, damaged_segments }
+    }
+}
+
+/// Writes the record the way it appears in the puzzle input, e.g. `???.### 1,1,3`.
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let springs = self.springs.iter().map(Spring::to_char).collect::<String>();
+        let damaged_segments = self.damaged_segments.iter()
+            .map(|segment| segment.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        write!(f, "{} {}", springs, damaged_segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_rejects_empty_segments() {
+        let springs = vec![Spring::Unknown; 3];
+
+        assert_eq!(Record::new(springs.clone(), vec![1,0,1]), Err(EmptySegment { index: 1 }));
+        assert!(Record::new(springs, vec![]).is_ok());
+    }
+
+    #[test]
+    fn unfold_repeats_record_with_separators() {
+        let record = ".# 1".parse::<Record>().unwrap();
+        assert_eq!(record.unfold(5).to_string(), ".#?.#?.#?.#?.# 1,1,1,1,1");
+
+        let record = "???.### 1,1,3".parse::<Record>().unwrap();
+        assert_eq!(record.unfold(5).to_string(), "???.###????.###????.###????.###????.### 1,1,3,1,1,3,1,1,3,1,1,3,1,1,3");
+    }
+}
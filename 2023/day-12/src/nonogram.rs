@@ -0,0 +1,385 @@
+//! Solving 2D nonograms, whose row and column clues are each a record's
+//! damaged segments.
+//!
+//! A puzzle is written as a `rows` section and a `columns` section of clues,
+//! one line per clue (`0` for a line with nothing filled in), optionally
+//! followed by a `grid` section of `#`, `.` and `?` giving cells already known:
+//!
+//! ```text
+//! rows
+//! 1,1
+//! 3
+//!
+//! columns
+//! 2
+//! 1
+//! 2
+//!
+//! grid
+//! #??
+//! ???
+//! ```
+
+use std::fmt::Display;
+
+use aoc_core::{parse_number, ParseError};
+
+use crate::deduction::{deduce_cells, Cell};
+use crate::nodes::{Record, Spring};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub row_clues: Vec<Vec<usize>>,
+    pub column_clues: Vec<Vec<usize>>,
+    pub grid: Grid,
+}
+
+/// Cells of a nonogram. Filled cells are damaged springs, empty cells
+/// operational ones, and cells not worked out yet unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    rows: Vec<Vec<Spring>>,
+}
+
+impl Grid {
+    pub fn unknown(height: usize, width: usize) -> Grid {
+        Grid { rows: vec![vec![Spring::Unknown; width]; height] }
+    }
+
+    pub fn rows(self: &Self) -> impl Iterator<Item = &[Spring]> {
+        self.rows.iter().map(|row| row.as_slice())
+    }
+
+    pub fn is_solved(self: &Self) -> bool {
+        self.rows.iter().flatten().all(|cell| *cell != Spring::Unknown)
+    }
+
+    fn column(self: &Self, x: usize) -> Vec<Spring> {
+        self.rows.iter().map(|row| row[x]).collect()
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().map(Spring::to_char).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// How many ways a puzzle can be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    Unique(Grid),
+    /// Two of the solutions, to show they differ.
+    Multiple(Grid, Grid),
+}
+
+impl Puzzle {
+    /// Fills in every cell that line deduction can settle, then guesses the
+    /// first unknown cell either way and carries on, until two solutions have
+    /// been found or every guess has been tried.
+    pub fn solve(self: &Self) -> Solutions {
+        let mut found = vec![];
+        self.search(self.grid.clone(), &mut found);
+
+        let mut found = found.into_iter();
+        match (found.next(), found.next()) {
+            (None, _) => Solutions::None,
+            (Some(solution), None) => Solutions::Unique(solution),
+            (Some(first), Some(second)) => Solutions::Multiple(first, second),
+        }
+    }
+
+    fn search(self: &Self, mut grid: Grid, found: &mut Vec<Grid>) {
+        if !self.deduce(&mut grid) {
+            return;
+        }
+
+        let unknown = grid.rows.iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|cell| *cell == Spring::Unknown).map(|x| (x, y)));
+
+        let Some((x, y)) = unknown else {
+            found.push(grid);
+            return;
+        };
+
+        for guess in [Spring::Damaged, Spring::Operational] {
+            if found.len() == 2 {
+                return;
+            }
+
+            let mut guessed = grid.clone();
+            guessed.rows[y][x] = guess;
+            self.search(guessed, found);
+        }
+    }
+
+    /// Applies line deduction to every row and column until nothing changes.
+    /// Returns false if some line has no arrangement left.
+    fn deduce(self: &Self, grid: &mut Grid) -> bool {
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for y in 0..grid.rows.len() {
+                let record = Record::new(grid.rows[y].clone(), self.row_clues[y].clone())
+                    .expect("Clues have no empty segments");
+                let Some(cells) = deduce_cells(&record) else {
+                    return false;
+                };
+                for (x, cell) in cells.iter().enumerate() {
+                    changed |= settle(&mut grid.rows[y][x], cell);
+                }
+            }
+
+            for x in 0..self.column_clues.len() {
+                let record = Record::new(grid.column(x), self.column_clues[x].clone())
+                    .expect("Clues have no empty segments");
+                let Some(cells) = deduce_cells(&record) else {
+                    return false;
+                };
+                for (y, cell) in cells.iter().enumerate() {
+                    changed |= settle(&mut grid.rows[y][x], cell);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Fills in an unknown grid cell if deduction settled it. Returns whether it changed.
+fn settle(grid_cell: &mut Spring, cell: &Cell) -> bool {
+    let settled = match cell {
+        Cell::Damaged => Spring::Damaged,
+        Cell::Working => Spring::Operational,
+        Cell::Ambiguous { .. } => return false,
+    };
+
+    let changed = *grid_cell != settled;
+    *grid_cell = settled;
+    changed
+}
+
+pub fn parse_puzzle(input: &str) -> Result<Puzzle, ParseError> {
+    // Blank lines after the last section would otherwise read as a missing `grid`
+    let input = input.trim_end();
+    let mut lines = input.lines().enumerate().map(|(index, line)| (index + 1, line)).peekable();
+
+    let row_clues = parse_clues(input, &mut lines, "rows")?;
+    let column_clues = parse_clues(input, &mut lines, "columns")?;
+
+    let grid = match lines.next() {
+        None => Grid::unknown(row_clues.len(), column_clues.len()),
+        Some((line_number, "grid")) => {
+            let rows = lines.by_ref()
+                .take_while(|(_, line)| !line.is_empty())
+                .map(|(line_number, line)| parse_grid_row(line_number, line, column_clues.len()))
+                .collect::<Result<Vec<Vec<Spring>>, ParseError>>()?;
+
+            if rows.len() != row_clues.len() {
+                return Err(ParseError::line(line_number, "grid", "expected a grid row for every row clue"));
+            }
+            Grid { rows }
+        }
+        Some((line_number, line)) => return Err(ParseError::line(line_number, line, "expected `grid`")),
+    };
+
+    if let Some((line_number, line)) = lines.next() {
+        return Err(ParseError::line(line_number, line, "expected the end of the puzzle"));
+    }
+
+    Ok(Puzzle { row_clues, column_clues, grid })
+}
+
+/// Parses a section of clues, starting with its `header` and ending at a blank
+/// line or the end of the input.
+fn parse_clues<'a>(
+    input: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    header: &str)
+    -> Result<Vec<Vec<usize>>, ParseError>
+{
+    match lines.next() {
+        Some((_, line)) if line == header => {}
+        Some((line_number, line)) => return Err(ParseError::line(line_number, line, &format!("expected `{}`", header))),
+        None => return Err(ParseError::end_of_input(input, &format!("expected `{}`", header))),
+    }
+
+    let clues = lines
+        .take_while(|(_, line)| !line.is_empty())
+        .map(|(line_number, line)| parse_clue(line_number, line))
+        .collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
+
+    if clues.is_empty() {
+        return Err(ParseError::end_of_input(input, &format!("expected at least one clue under `{}`", header)));
+    }
+
+    Ok(clues)
+}
+
+fn parse_clue(line_number: usize, line: &str) -> Result<Vec<usize>, ParseError> {
+    if line == "0" {
+        return Ok(vec![]);
+    }
+
+    line.split(',')
+        .map(|num| {
+            let segment = parse_number::<usize>(line_number, line, num)?;
+            if segment == 0 {
+                return Err(ParseError::at(line_number, line, num, "only an empty line's clue can be 0"));
+            }
+            Ok(segment)
+        })
+        .collect()
+}
+
+fn parse_grid_row(line_number: usize, line: &str, width: usize) -> Result<Vec<Spring>, ParseError> {
+    let row = line.char_indices()
+        .map(|(i, c)| Spring::from_char(c)
+            .ok_or_else(|| ParseError::at(line_number, line, &line[i..i + c.len_utf8()], "expected `.`, `#` or `?`")))
+        .collect::<Result<Vec<Spring>, ParseError>>()?;
+
+    if row.len() != width {
+        return Err(ParseError::line(line_number, line, "expected a cell for every column clue"));
+    }
+
+    Ok(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::springs;
+
+    const PLUS: &str = "rows
+1
+1
+5
+1
+1
+
+columns
+1
+1
+5
+1
+1";
+
+    // Line deduction alone gets stuck with 13 cells unknown
+    const NEEDS_GUESSING: &str = "rows
+1,2
+1,1
+1,2
+1,1
+1,2
+
+columns
+3
+1,1
+1
+3,1
+1,2";
+
+    const TWO_WAYS: &str = "rows
+2
+1,1
+2
+
+columns
+2
+1,1
+2
+";
+
+    fn grid(rows: &[&str]) -> Grid {
+        Grid { rows: rows.iter().map(|row| springs(row)).collect() }
+    }
+
+    #[test]
+    fn parse_puzzle_reads_clues_and_grid() {
+        let puzzle = parse_puzzle(&format!("{}\ngrid\n#??\n???\n?.?", TWO_WAYS)).unwrap();
+
+        assert_eq!(puzzle.row_clues, vec![vec![2], vec![1, 1], vec![2]]);
+        assert_eq!(puzzle.column_clues, vec![vec![2], vec![1, 1], vec![2]]);
+        assert_eq!(puzzle.grid, grid(&["#??", "???", "?.?"]));
+
+        let puzzle = parse_puzzle("rows\n0\n\ncolumns\n0\n0").unwrap();
+        assert_eq!(puzzle.row_clues, vec![Vec::<usize>::new()]);
+        assert_eq!(puzzle.grid, Grid::unknown(1, 2));
+    }
+
+    #[test]
+    fn parse_puzzle_ignores_trailing_blank_lines() {
+        let puzzle = parse_puzzle(&format!("{}\ngrid\n#??\n???\n?.?\n\n\n", TWO_WAYS)).unwrap();
+        assert_eq!(puzzle.grid, grid(&["#??", "???", "?.?"]));
+
+        let puzzle = parse_puzzle("rows\n0\n\ncolumns\n0\n0\n\n\n").unwrap();
+        assert_eq!(puzzle.grid, Grid::unknown(1, 2));
+    }
+
+    #[test]
+    fn parse_puzzle_reports_malformed_puzzles() {
+        let error = parse_puzzle("rows\n1\n\ncols\n1").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (4, "expected `columns`"));
+
+        let error = parse_puzzle("rows\n1,x\n\ncolumns\n1").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+
+        let error = parse_puzzle("rows\n1\n\ncolumns\n1\n\ngrid\n#?").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (8, "#?"));
+
+        let error = parse_puzzle("rows\n1\n\ncolumns\n1\n\ngrid\n*").unwrap_err();
+        assert_eq!((error.line, error.column), (8, 1));
+    }
+
+    #[test]
+    fn solve_by_line_deduction() {
+        let solutions = parse_puzzle(PLUS).unwrap().solve();
+
+        assert_eq!(solutions, Solutions::Unique(grid(&["..#..", "..#..", "#####", "..#..", "..#.."])));
+    }
+
+    #[test]
+    fn solve_by_guessing() {
+        let puzzle = parse_puzzle(NEEDS_GUESSING).unwrap();
+
+        let mut stuck = puzzle.grid.clone();
+        assert!(puzzle.deduce(&mut stuck));
+        assert!(!stuck.is_solved());
+
+        assert_eq!(puzzle.solve(), Solutions::Unique(grid(&[".#.##", "#..#.", "#.##.", "#...#", ".#.##"])));
+    }
+
+    #[test]
+    fn solve_reports_multiple_solutions() {
+        let Solutions::Multiple(first, second) = parse_puzzle(TWO_WAYS).unwrap().solve() else {
+            panic!("expected more than one solution");
+        };
+
+        assert_eq!(first, grid(&["##.", "#.#", ".##"]));
+        assert_eq!(second, grid(&[".##", "#.#", "##."]));
+    }
+
+    #[test]
+    fn solve_with_partial_grid() {
+        let puzzle = parse_puzzle(&format!("{}\ngrid\n#??\n???\n???", TWO_WAYS)).unwrap();
+
+        assert_eq!(puzzle.solve(), Solutions::Unique(grid(&["##.", "#.#", ".##"])));
+    }
+
+    #[test]
+    fn solve_reports_contradictions() {
+        assert_eq!(parse_puzzle("rows\n1\n\ncolumns\n0").unwrap().solve(), Solutions::None);
+        assert_eq!(parse_puzzle(&format!("{}\ngrid\n#.#\n???\n???", TWO_WAYS)).unwrap().solve(), Solutions::None);
+    }
+
+    #[test]
+    fn grid_prints_one_row_per_line() {
+        assert_eq!(grid(&["#.", ".#"]).to_string(), "#.\n.#\n");
+    }
+}
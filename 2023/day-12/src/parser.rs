@@ -1,19 +1,42 @@
-pub fn parse_input(input: &str) -> Vec<(&str, Vec<usize>)> {
+use std::str::FromStr;
+
+use aoc_core::{parse_number, ParseError};
+
+use crate::nodes::{Record, Spring};
+
+pub fn parse_input(input: &str) -> Result<Vec<Record>, ParseError> {
     input.lines()
-        .map(parse_line)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| parse_line(index + 1, line))
         .collect()
 }
 
-fn parse_line(line: &str) -> (&str, Vec<usize>) {
-    let parts = line.split(' ').collect::<Vec<&str>>();
-    let mask = parts[0];
-    let damaged_segments = parts[1]
-        .split(',')
-        .map(|num| 
-            num.parse::<usize>().unwrap())
-        .collect::<Vec<usize>>();
+fn parse_line(line_number: usize, line: &str) -> Result<Record, ParseError> {
+    let (mask, segments) = line.split_once(' ')
+        .ok_or_else(|| ParseError::line(line_number, line, "expected a mask and damaged segments separated by a space"))?;
 
-    (mask, damaged_segments)
+    let springs = mask.char_indices()
+        .map(|(i, c)| Spring::from_char(c)
+            .ok_or_else(|| ParseError::at(line_number, line, &mask[i..i + c.len_utf8()], "expected `.`, `#` or `?`")))
+        .collect::<Result<Vec<Spring>, ParseError>>()?;
+
+    let segments = segments.split(',').collect::<Vec<&str>>();
+    let damaged_segments = segments.iter()
+        .map(|num| parse_number::<usize>(line_number, line, num))
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+
+    Record::new(springs, damaged_segments)
+        .map_err(|e| ParseError::at(line_number, line, segments[e.index], "damaged segments cannot be empty"))
+}
+
+/// Parses a single record, written as it is in the puzzle input.
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Record, ParseError> {
+        parse_line(1, line)
+    }
 }
 
 #[cfg(test)]
@@ -25,10 +48,11 @@ mod tests {
     #[test]
     fn parse_line_returns_mask_and_damaged_segments() {
         let line = "?#.??????#??#?#?#?#? 1,1,15";
-        let (mask, damaged_segments) = parse_line(line);
+        let record = parse_line(1, line).unwrap();
 
-        assert_eq!(mask, "?#.??????#??#?#?#?#?");
-        assert_contains_exactly!(damaged_segments, vec![1, 1, 15]);
+        assert_eq!(record.springs()[..3], [Spring::Unknown, Spring::Damaged, Spring::Operational]);
+        assert_eq!(record.to_string(), line);
+        assert_contains_exactly!(record.damaged_segments().to_vec(), vec![1, 1, 15]);
     }
 
     #[test]
@@ -40,16 +64,44 @@ mod tests {
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
-        let records = parse_input(input);
+        let records = parse_input(input).unwrap();
 
         assert_eq!(records.len(), 6);
 
-        let (mask, damaged_segments) = &records[0];
-        assert_eq!(*mask, "???.###");
-        assert_contains_exactly!(damaged_segments, vec![1, 1, 3]);
+        assert_eq!(records[0].to_string(), "???.### 1,1,3");
+        assert_contains_exactly!(records[0].damaged_segments().to_vec(), vec![1, 1, 3]);
+
+        let record = records.iter().last().unwrap();
+        assert_eq!(record.to_string(), "?###???????? 3,2,1");
+        assert_contains_exactly!(record.damaged_segments().to_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn parse_line_reports_missing_segments() {
+        let error = parse_line(4, "???.###").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.text, "???.###");
+    }
+
+    #[test]
+    fn parse_line_reports_unknown_springs() {
+        let error = parse_line(1, "??x.### 1,1,3").unwrap_err();
+
+        assert_eq!(error, ParseError {
+            line: 1,
+            column: 3,
+            text: String::from("x"),
+            message: String::from("expected `.`, `#` or `?`"),
+        });
+    }
+
+    #[test]
+    fn parse_input_reports_bad_segments() {
+        let error = parse_input("???.### 1,1,3\n.??..??...?##. 1,,3").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 18, "expected a number"));
 
-        let (mask, damaged_segments) = records.iter().last().unwrap();
-        assert_eq!(*mask, "?###????????");
-        assert_contains_exactly!(damaged_segments, vec![3, 2, 1]);
+        let error = parse_input("???.### 1,0,3").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 11, "0"));
     }
 }
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use aoc_core::Part;
+
+/// The real site; overridden with `--base-url` or `AOC_BASE_URL`, e.g. to
+/// point at a local stand-in server.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the value of the site's `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/MadMathMike/AdventOfCode aoc-cli";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// The server answered with an error status, e.g. 404 for a puzzle that
+    /// is not unlocked yet or 400 for an expired session.
+    Status(u16, String),
+    /// The request never got an answer.
+    Transport(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Status(status, body) => write!(f, "server returned {}: {}", status, body.trim()),
+            ClientError::Transport(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(error: ureq::Error) -> ClientError {
+        match error {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(transport) => ClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// Talks to the puzzle site as the user whose session token it holds.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        let response = self.agent.get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+
+        response.into_string()
+            .map_err(|e| ClientError::Transport(e.to_string()))
+    }
+
+    /// Posts an answer and returns the page the site responds with.
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        let response = self.agent.post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        response.into_string()
+            .map_err(|e| ClientError::Transport(e.to_string()))
+    }
+}
+
+/// Reads the session token from `path`, or from `AOC_SESSION` if no path is given.
+pub fn load_session(path: Option<&Path>) -> Result<String, String> {
+    match path {
+        Some(path) => std::fs::read_to_string(path)
+            .map(|session| session.trim().to_string())
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        None => std::env::var(SESSION_VAR)
+            .map_err(|_| format!("No session token: set {} or pass --session-file", SESSION_VAR)),
+    }
+}
+
+pub fn base_url() -> String {
+    std::env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL))
+}
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use aoc_core::input::input_path;
+
+use crate::client::{Client, ClientError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already in the cache, so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Client(ClientError),
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Client(e) => write!(f, "{}", e),
+            FetchError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Makes sure the input for `year`/`day` is in `inputs_dir`, downloading
+/// it only if it is not there yet. Inputs never change once published, so a
+/// cached input is never fetched again.
+pub fn fetch(client: &Client, inputs_dir: &Path, year: u16, day: u8) -> Result<Fetched, FetchError> {
+    let path = input_path(inputs_dir, year, day);
+
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(year, day).map_err(FetchError::Client)?;
+
+    // Write to a temporary file first so an interrupted download never
+    // leaves a partial input behind that would be mistaken for a cached one.
+    let dir = path.parent().unwrap();
+    let partial = path.with_extension("txt.partial");
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&partial, input))
+        .and_then(|_| std::fs::rename(&partial, &path))
+        .map_err(|e| FetchError::Io(path.clone(), e))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::{serve, temp_dir};
+
+    use super::*;
+
+    #[test]
+    fn fetch_downloads_input_into_cache() {
+        let server = serve(vec![(200, "???.### 1")]);
+        let inputs_dir = temp_dir("fetch-downloads");
+        let client = Client::new(&server.base_url, "abc123");
+
+        let fetched = fetch(&client, &inputs_dir, 2023, 12).unwrap();
+
+        let path = inputs_dir.join("2023").join("12.txt");
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "???.### 1");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/12/input HTTP/1.1\r\n"));
+        assert!(requests[0].to_lowercase().contains("cookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn fetch_never_refetches_cached_input() {
+        let server = serve(vec![]);
+        let inputs_dir = temp_dir("fetch-cached");
+        let path = inputs_dir.join("2023").join("05.txt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "seeds: 1").unwrap();
+        let client = Client::new(&server.base_url, "abc123");
+
+        let fetched = fetch(&client, &inputs_dir, 2023, 5).unwrap();
+
+        assert_eq!(fetched, Fetched::Cached(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "seeds: 1");
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn fetch_error_leaves_nothing_in_cache() {
+        let server = serve(vec![(404, "Not found")]);
+        let inputs_dir = temp_dir("fetch-error");
+        let client = Client::new(&server.base_url, "abc123");
+
+        let error = fetch(&client, &inputs_dir, 2023, 25).unwrap_err();
+
+        assert!(matches!(error, FetchError::Client(ClientError::Status(404, _))));
+        assert!(!inputs_dir.join("2023").join("25.txt").exists());
+    }
+}
@@ -0,0 +1,307 @@
+mod client;
+mod fetch;
+mod registry;
+mod scaffold;
+mod submit;
+mod table;
+#[cfg(test)]
+mod test_server;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::SystemTime;
+
+use aoc_core::answers::Answers;
+use aoc_core::input::InputSource;
+use aoc_core::Part;
+use clap::{Args, Parser, Subcommand};
+
+use crate::client::Client;
+use crate::fetch::Fetched;
+use crate::submit::Registry;
+
+/// Where submissions remember how long the site asked us to wait, inside the inputs directory.
+const WAIT_UNTIL_FILE: &str = ".submit-wait-until";
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, every day of a year, or every day there is
+    Run {
+        year: Option<u16>,
+        day: Option<u8>,
+        /// Only run this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Download a day's puzzle input into the inputs directory, unless it is already there
+    Fetch {
+        year: u16,
+        day: u8,
+        /// Directory holding inputs as <year>/<day>.txt [default: $AOC_INPUTS_DIR or inputs]
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Submit an answer (by default, the one the solution gives) and record the verdict.
+    /// Exits with failure unless the site accepted the answer
+    Submit {
+        year: u16,
+        day: u8,
+        /// The part being answered (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// The answer to submit [default: solve the day's input]
+        answer: Option<String>,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Create the crate for a new day and register it with the workspace and the runner
+    New {
+        year: u16,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The workspace root
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Read the input from this file (`-` for stdin) instead of the
+    /// inputs directory; only valid for a single day
+    #[arg(long, requires = "day")]
+    input: Option<String>,
+    /// Directory holding inputs as <year>/<day>.txt [default: $AOC_INPUTS_DIR or inputs]
+    #[arg(long)]
+    inputs_dir: Option<PathBuf>,
+    /// Use this answers file instead of the one next to the input
+    #[arg(long, requires = "day")]
+    answers: Option<PathBuf>,
+}
+
+impl InputArgs {
+    fn inputs_dir(&self) -> PathBuf {
+        self.inputs_dir.clone().unwrap_or_else(aoc_core::input::inputs_dir)
+    }
+
+    fn source(&self) -> InputSource {
+        match &self.input {
+            Some(input) => InputSource::from_arg(input),
+            None => InputSource::Directory(self.inputs_dir()),
+        }
+    }
+
+    fn answers_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        self.answers.clone().or_else(|| self.source().answers_path(year, day))
+    }
+}
+
+#[derive(Args)]
+struct SiteArgs {
+    /// Site to talk to [default: $AOC_BASE_URL or https://adventofcode.com]
+    #[arg(long)]
+    base_url: Option<String>,
+    /// File holding the session cookie [default: read $AOC_SESSION]
+    #[arg(long)]
+    session_file: Option<PathBuf>,
+}
+
+impl SiteArgs {
+    fn client(&self) -> Result<Client, String> {
+        let base_url = self.base_url.clone().unwrap_or_else(client::base_url);
+        let session = client::load_session(self.session_file.as_deref())?;
+
+        Ok(Client::new(&base_url, &session))
+    }
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run { year, day, part, input } => run(year, day, part, &input),
+        Command::Fetch { year, day, inputs_dir, site } => {
+            let inputs_dir = inputs_dir.unwrap_or_else(aoc_core::input::inputs_dir);
+
+            fetch(year, day, &inputs_dir, &site)
+        }
+        Command::Submit { year, day, part, answer, input, site } => {
+            let part = if part == 1 { Part::One } else { Part::Two };
+
+            submit(year, day, part, answer, &input, &site)
+        }
+        Command::New { year, day, root } => match scaffold::scaffold(&root, year, day) {
+            Ok(crate_dir) => {
+                println!("Created {}", crate_dir.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Could not create {} day {}: {}", year, day, e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+fn run(year: Option<u16>, day: Option<u8>, part: Option<u8>, input_args: &InputArgs) -> ExitCode {
+    let parts = match part {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let solvers = registry::select(registry::solvers(), year, day);
+    if solvers.is_empty() {
+        eprintln!("No solutions found for the requested year/day");
+        return ExitCode::FAILURE;
+    }
+
+    let source = input_args.source();
+    let mut reports = Vec::new();
+    let mut failed = false;
+
+    for solver in solvers.iter() {
+        let (year, day) = (solver.year(), solver.day());
+
+        let input = match source.read(year, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Skipping {} day {}: {}", year, day, e);
+                failed = true;
+                continue;
+            }
+        };
+
+        let answers = match input_args.answers_path(year, day) {
+            Some(path) => Answers::load(&path).unwrap_or_else(|e| {
+                eprintln!("Not checking {} day {}: {}", year, day, e);
+                Answers::default()
+            }),
+            None => Answers::default(),
+        };
+
+        let report = match solver.run(&input, &parts) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("Could not parse {} day {} input: {}", year, day, e);
+                failed = true;
+                continue;
+            }
+        };
+
+        failed |= report.parts.iter()
+            .filter_map(|part| part.answer.as_ref().map(|answer| answers.check(part.part, answer)))
+            .any(|verdict| verdict.is_wrong());
+
+        reports.push((report, answers));
+    }
+
+    print!("{}", table::render(&reports));
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch(year: u16, day: u8, inputs_dir: &Path, site: &SiteArgs) -> ExitCode {
+    let client = match site.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match fetch::fetch(&client, inputs_dir, year, day) {
+        Ok(Fetched::Cached(path)) => {
+            println!("Already have {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Downloaded {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Could not fetch {} day {}: {}", year, day, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(year: u16, day: u8, part: Part, answer: Option<String>, input_args: &InputArgs, site: &SiteArgs) -> ExitCode {
+    let Some(answers_path) = input_args.answers_path(year, day) else {
+        eprintln!("Input from stdin has no answers file; pass --answers");
+        return ExitCode::FAILURE;
+    };
+
+    let answer = match answer.map_or_else(|| solve(year, day, part, &input_args.source()), Ok) {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = match site.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let registry = Registry {
+        answers_path: &answers_path,
+        wait_until_path: &input_args.inputs_dir().join(WAIT_UNTIL_FILE),
+    };
+
+    println!("Submitting {} for {} day {} part {}", answer, year, day, part);
+
+    match submit::submit(&client, &registry, year, day, part, &answer, SystemTime::now()) {
+        Ok(response) => {
+            println!("{}", response.outcome);
+            if let Some(wait) = response.wait {
+                println!("Next submission allowed in {}s", wait.as_secs());
+            }
+
+            if response.outcome.is_accepted() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("Not submitted: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the day's solution to get the answer to submit.
+fn solve(year: u16, day: u8, part: Part, source: &InputSource) -> Result<String, String> {
+    let solver = registry::select(registry::solvers(), Some(year), Some(day))
+        .pop()
+        .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+
+    let input = source.read(year, day)
+        .map_err(|e| format!("Could not read input: {}", e))?;
+
+    let report = solver.run(&input, &[part])
+        .map_err(|e| format!("Could not parse input: {}", e))?;
+
+    report.parts[0].answer.clone()
+        .ok_or_else(|| format!("No solution for {} day {} part {}", year, day, part))
+}
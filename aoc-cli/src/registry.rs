@@ -0,0 +1,57 @@
+use aoc_core::{solver, Solver};
+
+/// Every day the runner knows how to solve.
+pub fn solvers() -> Vec<Box<dyn Solver>> {
+    vec![
+        solver::<day_01::Day01>(),
+        solver::<day_05::Day05>(),
+        solver::<day_10::Day10>(),
+        solver::<day_12::Day12>(),
+    ]
+}
+
+/// Keeps the solvers matching `year` and `day`, where `None` matches any.
+pub fn select(solvers: Vec<Box<dyn Solver>>, year: Option<u16>, day: Option<u8>) -> Vec<Box<dyn Solver>> {
+    solvers.into_iter()
+        .filter(|solver| year.is_none_or(|year| solver.year() == year))
+        .filter(|solver| day.is_none_or(|day| solver.day() == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(solvers: &[Box<dyn Solver>]) -> Vec<(u16, u8)> {
+        solvers.iter()
+            .map(|solver| (solver.year(), solver.day()))
+            .collect()
+    }
+
+    #[test]
+    fn select_without_filters_keeps_every_day() {
+        let selected = select(solvers(), None, None);
+
+        assert_eq!(days(&selected), days(&solvers()));
+    }
+
+    #[test]
+    fn solvers_are_in_order_without_duplicates() {
+        let days = days(&solvers());
+
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn select_by_year_and_day_keeps_one_day() {
+        let selected = select(solvers(), Some(2023), Some(5));
+
+        assert_eq!(days(&selected), vec![(2023, 5)]);
+    }
+
+    #[test]
+    fn select_unknown_day_keeps_nothing() {
+        assert!(select(solvers(), Some(2023), Some(2)).is_empty());
+        assert!(select(solvers(), Some(2022), None).is_empty());
+    }
+}
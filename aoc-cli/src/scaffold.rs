@@ -0,0 +1,239 @@
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TEMPLATES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.template")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.template")),
+    ("src/parser.rs", include_str!("../templates/parser.rs.template")),
+    ("src/main.rs", include_str!("../templates/main.rs.template")),
+    ("benches/solution.rs", include_str!("../templates/solution.rs.template")),
+];
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day's directory is already there.
+    AlreadyExists(PathBuf),
+    /// Another year already has a crate named after this day.
+    NameTaken(String),
+    /// A file that has to be edited to register the day is not laid out as expected.
+    NoInsertionPoint(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::NameTaken(name) => write!(f, "the workspace already has a package named {}", name),
+            ScaffoldError::NoInsertionPoint(path) => write!(f, "could not find where to register the day in {}", path.display()),
+            ScaffoldError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Creates the crate for a new day under `root` (the workspace root) and
+/// registers it with the workspace and the runner. Returns the crate's directory.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<PathBuf, ScaffoldError> {
+    let name = format!("day-{:02}", day);
+    let crate_dir = root.join(year.to_string()).join(&name);
+    let member = format!("{}/{}", year, name);
+
+    if crate_dir.exists() {
+        return Err(ScaffoldError::AlreadyExists(crate_dir));
+    }
+
+    // Check every edit can be made before touching anything, so a failure
+    // never leaves a half-registered day behind.
+    let workspace_manifest = root.join("Cargo.toml");
+    let workspace_text = read(&workspace_manifest)?;
+    if workspace_text.lines().any(|line| line.trim().trim_end_matches(',').trim_matches('"').ends_with(&format!("/{}", name))) {
+        return Err(ScaffoldError::NameTaken(name));
+    }
+    let workspace_text = insert_sorted(
+        &workspace_text,
+        |line| line.trim_start().starts_with('"') && line.contains("/day-"),
+        &format!("    \"{}\",", member),
+    ).ok_or_else(|| ScaffoldError::NoInsertionPoint(workspace_manifest.clone()))?;
+
+    let runner_manifest = root.join("aoc-cli").join("Cargo.toml");
+    let runner_text = insert_sorted(
+        &read(&runner_manifest)?,
+        |line| line.starts_with("day-"),
+        &format!("{} = {{ path = \"../{}\" }}", name, member),
+    ).ok_or_else(|| ScaffoldError::NoInsertionPoint(runner_manifest.clone()))?;
+
+    let registry = root.join("aoc-cli").join("src").join("registry.rs");
+    let registry_text = insert_sorted(
+        &read(&registry)?,
+        |line| line.trim_start().starts_with("solver::<day_"),
+        &format!("        solver::<day_{:02}::Day{:02}>(),", day, day),
+    ).ok_or_else(|| ScaffoldError::NoInsertionPoint(registry.clone()))?;
+
+    for (file, template) in TEMPLATES {
+        write(&crate_dir.join(file), &render(template, year, day))?;
+    }
+    write(&crate_dir.join("sample_input.txt"), "")?;
+
+    write(&workspace_manifest, &workspace_text)?;
+    write(&runner_manifest, &runner_text)?;
+    write(&registry, &registry_text)?;
+
+    Ok(crate_dir)
+}
+
+fn render(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY_NUMBER}}", &day.to_string())
+        .replace("{{DAY}}", &format!("{:02}", day))
+}
+
+/// Inserts `new_line` into the run of lines matching `is_entry`, keeping the
+/// run sorted. Returns `None` if there is no such run to insert into.
+fn insert_sorted(text: &str, is_entry: impl Fn(&str) -> bool, new_line: &str) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<&str>>();
+
+    let last_entry = lines.iter().rposition(|line| is_entry(line))?;
+    let index = lines.iter()
+        .position(|line| is_entry(line) && line.trim() > new_line.trim())
+        .unwrap_or(last_entry + 1);
+    lines.insert(index, new_line);
+
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+
+    Some(result)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, contents))
+        .map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::temp_dir;
+
+    use super::*;
+
+    const WORKSPACE_MANIFEST: &str = "[workspace]
+members = [
+    \"aoc-cli\",
+    \"aoc-core\",
+    \"2023/day-05\",
+    \"2023/day-10\",
+]
+";
+
+    const RUNNER_MANIFEST: &str = "[dependencies]
+aoc-core.workspace = true
+day-05 = { path = \"../2023/day-05\" }
+day-10 = { path = \"../2023/day-10\" }
+ureq.workspace = true
+";
+
+    const REGISTRY: &str = "pub fn solvers() -> Vec<Box<dyn Solver>> {
+    vec![
+        solver::<day_05::Day05>(),
+        solver::<day_10::Day10>(),
+    ]
+}
+";
+
+    fn workspace(name: &str, workspace_manifest: &str, runner_manifest: &str, registry: &str) -> PathBuf {
+        let root = temp_dir(name);
+        std::fs::create_dir_all(root.join("aoc-cli").join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), workspace_manifest).unwrap();
+        std::fs::write(root.join("aoc-cli").join("Cargo.toml"), runner_manifest).unwrap();
+        std::fs::write(root.join("aoc-cli").join("src").join("registry.rs"), registry).unwrap();
+        root
+    }
+
+    fn read(root: &Path, file: &str) -> String {
+        std::fs::read_to_string(root.join(file)).unwrap()
+    }
+
+    #[test]
+    fn scaffold_creates_and_registers_day() {
+        let root = workspace("scaffold-creates", WORKSPACE_MANIFEST, RUNNER_MANIFEST, REGISTRY);
+
+        let crate_dir = scaffold(&root, 2023, 7).unwrap();
+
+        assert_eq!(crate_dir, root.join("2023").join("day-07"));
+        for file in ["Cargo.toml", "src/lib.rs", "src/parser.rs", "src/main.rs", "benches/solution.rs", "sample_input.txt"] {
+            assert!(crate_dir.join(file).exists(), "{} was not created", file);
+        }
+
+        assert!(read(&crate_dir, "Cargo.toml").contains("name = \"day-07\""));
+        let lib = read(&crate_dir, "src/lib.rs");
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const YEAR: u16 = 2023;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(!lib.contains("{{"));
+
+        assert!(read(&root, "Cargo.toml").contains("    \"2023/day-05\",\n    \"2023/day-07\",\n    \"2023/day-10\",\n"));
+        assert!(read(&root, "aoc-cli/Cargo.toml")
+            .contains("day-05 = { path = \"../2023/day-05\" }\nday-07 = { path = \"../2023/day-07\" }\nday-10"));
+        assert!(read(&root, "aoc-cli/src/registry.rs")
+            .contains("solver::<day_05::Day05>(),\n        solver::<day_07::Day07>(),\n        solver::<day_10::Day10>(),"));
+    }
+
+    #[test]
+    fn scaffold_appends_after_last_day() {
+        let root = workspace("scaffold-appends", WORKSPACE_MANIFEST, RUNNER_MANIFEST, REGISTRY);
+
+        scaffold(&root, 2023, 13).unwrap();
+
+        assert!(read(&root, "Cargo.toml").contains("    \"2023/day-10\",\n    \"2023/day-13\",\n]"));
+        assert!(read(&root, "aoc-cli/src/registry.rs")
+            .contains("solver::<day_10::Day10>(),\n        solver::<day_13::Day13>(),\n    ]"));
+    }
+
+    /// Guards against the real files drifting from the layout `scaffold` expects.
+    #[test]
+    fn scaffold_understands_this_workspace() {
+        let registry = include_str!("registry.rs");
+        let root = workspace("scaffold-real", include_str!("../../Cargo.toml"), include_str!("../Cargo.toml"), registry);
+        let day = (1..=25)
+            .find(|day| !registry.contains(&format!("day_{:02}::", day)))
+            .unwrap();
+
+        scaffold(&root, 2023, day).unwrap();
+
+        let line = format!("solver::<day_{:02}::Day{:02}>(),", day, day);
+        assert!(read(&root, "aoc-cli/src/registry.rs").contains(&line));
+    }
+
+    #[test]
+    fn scaffold_refuses_existing_day() {
+        let root = workspace("scaffold-existing", WORKSPACE_MANIFEST, RUNNER_MANIFEST, REGISTRY);
+        let before = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+        assert!(matches!(scaffold(&root, 2022, 5), Err(ScaffoldError::NameTaken(_))));
+        std::fs::create_dir_all(root.join("2023").join("day-05")).unwrap();
+        assert!(matches!(scaffold(&root, 2023, 5), Err(ScaffoldError::AlreadyExists(_))));
+
+        assert_eq!(std::fs::read_to_string(root.join("Cargo.toml")).unwrap(), before);
+        assert!(!root.join("2022").exists());
+    }
+
+    #[test]
+    fn insert_sorted_keeps_run_sorted() {
+        let text = "[\n    \"b\",\n    \"d\",\n]\n";
+        let is_entry = |line: &str| line.trim_start().starts_with('"');
+
+        assert_eq!(insert_sorted(text, is_entry, "    \"c\",").unwrap(), "[\n    \"b\",\n    \"c\",\n    \"d\",\n]\n");
+        assert_eq!(insert_sorted(text, is_entry, "    \"a\",").unwrap(), "[\n    \"a\",\n    \"b\",\n    \"d\",\n]\n");
+        assert_eq!(insert_sorted(text, is_entry, "    \"e\",").unwrap(), "[\n    \"b\",\n    \"d\",\n    \"e\",\n]\n");
+        assert_eq!(insert_sorted("[]", is_entry, "    \"e\","), None);
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_core::answers::{Answers, Feedback, Verdict};
+use aoc_core::Part;
+
+use crate::client::{Client, ClientError};
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Feedback(Feedback),
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// Submitted too soon after the previous answer, so it was not checked.
+    RateLimited,
+    /// The page did not look like any response we know, e.g. a login page.
+    Unrecognized,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub outcome: Outcome,
+    /// How long the site asks us to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+impl Outcome {
+    /// Whether the site checked the answer and accepted it.
+    pub fn is_accepted(&self) -> bool {
+        *self == Outcome::Feedback(Feedback::Correct)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Feedback(Feedback::Correct) => write!(f, "That's the right answer!"),
+            Outcome::Feedback(Feedback::Wrong) => write!(f, "That's not the right answer."),
+            Outcome::Feedback(Feedback::TooHigh) => write!(f, "That's not the right answer; it is too high."),
+            Outcome::Feedback(Feedback::TooLow) => write!(f, "That's not the right answer; it is too low."),
+            Outcome::AlreadySolved => write!(f, "This part is already solved."),
+            Outcome::RateLimited => write!(f, "Answered too recently; the answer was not checked."),
+            Outcome::Unrecognized => write!(f, "The site's response was not recognized."),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The previous submission asked us to wait this much longer.
+    RateLimited(Duration),
+    /// The answer is already known to be correct, so there is nothing to submit.
+    AlreadyCorrect,
+    /// The answer is already known to be wrong.
+    KnownWrong(Verdict),
+    Client(ClientError),
+    Io(PathBuf, io::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::RateLimited(wait) => write!(f, "wait {}s before submitting again", wait.as_secs()),
+            SubmitError::AlreadyCorrect => write!(f, "answer is already known to be correct"),
+            SubmitError::KnownWrong(verdict) => write!(f, "not resubmitting: {}", verdict),
+            SubmitError::Client(e) => write!(f, "{}", e),
+            SubmitError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Where submission state lives: the answers file for the input, and the
+/// file remembering when the site will next accept an answer.
+pub struct Registry<'a> {
+    pub answers_path: &'a Path,
+    pub wait_until_path: &'a Path,
+}
+
+/// Submits `answer` unless the answers file or the rate limit says it would
+/// be pointless, then records what the site said about it.
+pub fn submit(
+    client: &Client,
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+    now: SystemTime,
+) -> Result<Response, SubmitError> {
+    if let Some(wait) = load_wait_until(registry.wait_until_path)
+        .and_then(|wait_until| wait_until.duration_since(now).ok())
+    {
+        return Err(SubmitError::RateLimited(wait));
+    }
+
+    let mut answers = Answers::load(registry.answers_path)
+        .map_err(|e| SubmitError::Io(registry.answers_path.to_path_buf(), e))?;
+
+    match answers.check(part, answer) {
+        Verdict::Correct => return Err(SubmitError::AlreadyCorrect),
+        Verdict::Unknown => {}
+        verdict => return Err(SubmitError::KnownWrong(verdict)),
+    }
+
+    let body = client.submit(year, day, part, answer).map_err(SubmitError::Client)?;
+    let response = parse_response(&body);
+
+    if let Some(wait) = response.wait {
+        save_wait_until(registry.wait_until_path, now + wait)
+            .map_err(|e| SubmitError::Io(registry.wait_until_path.to_path_buf(), e))?;
+    }
+
+    if let Outcome::Feedback(feedback) = response.outcome {
+        answers.record(part, answer, feedback);
+        answers.save(registry.answers_path)
+            .map_err(|e| SubmitError::Io(registry.answers_path.to_path_buf(), e))?;
+    }
+
+    Ok(response)
+}
+
+/// Reads the outcome out of the page the site returns after a submission.
+pub fn parse_response(body: &str) -> Response {
+    let outcome = if body.contains("That's the right answer") {
+        Outcome::Feedback(Feedback::Correct)
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Outcome::Feedback(Feedback::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Outcome::Feedback(Feedback::TooLow)
+        } else {
+            Outcome::Feedback(Feedback::Wrong)
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if body.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognized
+    };
+
+    Response { outcome, wait: parse_wait(body) }
+}
+
+/// Understands "You have 1m 23s left to wait" (after answering too soon) and
+/// "please wait 5 minutes before trying again" (after a wrong answer).
+fn parse_wait(body: &str) -> Option<Duration> {
+    if let Some(end) = body.find(" left to wait") {
+        let start = body[..end].rfind("You have ")? + "You have ".len();
+
+        let seconds = body[start..end]
+            .split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let body = body.to_lowercase();
+    let start = body.find("please wait ")? + "please wait ".len();
+    let end = start + body[start..].find(" before trying again")?;
+    let mut words = body[start..end].split_whitespace();
+    let count = match words.next()? {
+        "one" => 1,
+        number => number.parse::<u64>().ok()?,
+    };
+
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(count * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(count)),
+        _ => None,
+    }
+}
+
+fn load_wait_until(path: &Path) -> Option<SystemTime> {
+    let seconds = std::fs::read_to_string(path).ok()?.trim().parse::<u64>().ok()?;
+
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn save_wait_until(path: &Path, wait_until: SystemTime) -> io::Result<()> {
+    // Round up so we never come back a fraction of a second too early.
+    let since_epoch = wait_until.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs() + u64::from(since_epoch.subsec_nanos() > 0);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, seconds.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::{serve, temp_dir};
+
+    use super::*;
+
+    const RIGHT: &str = "<article><p>That's the right answer!  You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  \
+        Please wait one minute before trying again.</p></article>";
+
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }
+
+    #[test]
+    fn only_a_correct_answer_is_accepted() {
+        assert!(Outcome::Feedback(Feedback::Correct).is_accepted());
+        assert!(!Outcome::Feedback(Feedback::TooLow).is_accepted());
+        assert!(!Outcome::AlreadySolved.is_accepted());
+        assert!(!Outcome::RateLimited.is_accepted());
+        assert!(!Outcome::Unrecognized.is_accepted());
+    }
+
+    #[test]
+    fn parse_response_recognizes_each_outcome() {
+        assert_eq!(parse_response(RIGHT), Response { outcome: Outcome::Feedback(Feedback::Correct), wait: None });
+        assert_eq!(parse_response(TOO_HIGH).outcome, Outcome::Feedback(Feedback::TooHigh));
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too low.").outcome,
+            Outcome::Feedback(Feedback::TooLow)
+        );
+        assert_eq!(
+            parse_response("That's not the right answer.  If you're stuck, ...").outcome,
+            Outcome::Feedback(Feedback::Wrong)
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level.  Did you already complete it?").outcome,
+            Outcome::AlreadySolved
+        );
+        assert_eq!(parse_response("<html>Log in</html>").outcome, Outcome::Unrecognized);
+    }
+
+    #[test]
+    fn parse_response_reads_wait_times() {
+        assert_eq!(parse_response(TOO_HIGH).wait, Some(Duration::from_secs(60)));
+        assert_eq!(
+            parse_response("please wait 5 minutes before trying again.").wait,
+            Some(Duration::from_secs(300))
+        );
+
+        let too_recent = parse_response("You gave an answer too recently; you have to wait after submitting \
+            an answer before trying again.  You have 1m 23s left to wait.");
+        assert_eq!(too_recent, Response { outcome: Outcome::RateLimited, wait: Some(Duration::from_secs(83)) });
+
+        assert_eq!(parse_response("You have 9s left to wait.").wait, Some(Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn submit_posts_answer_and_records_feedback() {
+        let server = serve(vec![(200, TOO_HIGH)]);
+        let dir = temp_dir("submit-records");
+        let registry = Registry {
+            answers_path: &dir.join("10.answers.toml"),
+            wait_until_path: &dir.join(".wait-until"),
+        };
+        let client = Client::new(&server.base_url, "abc123");
+
+        let response = submit(&client, &registry, 2023, 10, Part::Two, "400", now()).unwrap();
+
+        assert_eq!(response.outcome, Outcome::Feedback(Feedback::TooHigh));
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2023/day/10/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=400"));
+
+        let answers = Answers::load(registry.answers_path).unwrap();
+        assert_eq!(answers.part2.too_high, vec![String::from("400")]);
+        assert_eq!(load_wait_until(registry.wait_until_path), Some(now() + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn submit_refuses_known_wrong_and_out_of_bound_answers() {
+        let server = serve(vec![]);
+        let dir = temp_dir("submit-refuses");
+        let registry = Registry {
+            answers_path: &dir.join("10.answers.toml"),
+            wait_until_path: &dir.join(".wait-until"),
+        };
+        std::fs::write(registry.answers_path, "[part1]\nanswer = 6701\n\n[part2]\nwrong = [314]\ntoo_low = [200]\n").unwrap();
+        let client = Client::new(&server.base_url, "abc123");
+
+        let submit = |part, answer| submit(&client, &registry, 2023, 10, part, answer, now());
+
+        assert!(matches!(submit(Part::One, "6701"), Err(SubmitError::AlreadyCorrect)));
+        assert!(matches!(submit(Part::One, "6702"), Err(SubmitError::KnownWrong(Verdict::Wrong { .. }))));
+        assert!(matches!(submit(Part::Two, "314"), Err(SubmitError::KnownWrong(Verdict::KnownWrong))));
+        assert!(matches!(submit(Part::Two, "150"), Err(SubmitError::KnownWrong(Verdict::TooLow { bound: 200 }))));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn submit_waits_out_rate_limit() {
+        let server = serve(vec![]);
+        let dir = temp_dir("submit-waits");
+        let registry = Registry {
+            answers_path: &dir.join("10.answers.toml"),
+            wait_until_path: &dir.join(".wait-until"),
+        };
+        save_wait_until(registry.wait_until_path, now() + Duration::from_secs(30)).unwrap();
+        let client = Client::new(&server.base_url, "abc123");
+
+        let result = submit(&client, &registry, 2023, 10, Part::Two, "303", now());
+
+        assert!(matches!(result, Err(SubmitError::RateLimited(wait)) if wait == Duration::from_secs(30)));
+        assert!(server.requests().is_empty());
+    }
+}
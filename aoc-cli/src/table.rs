@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use aoc_core::answers::Answers;
+use aoc_core::Report;
+
+const HEADERS: [&str; 7] = ["Year", "Day", "Part", "Answer", "Check", "Parse", "Solve"];
+
+/// Lays the reports out as a table with one row per part, each answer
+/// checked against what is known about it, followed by the total time spent
+/// parsing and solving.
+pub fn render(reports: &[(Report, Answers)]) -> String {
+    let mut rows = Vec::<[String; 7]>::new();
+    let mut total = Duration::ZERO;
+
+    for (report, answers) in reports {
+        total += report.parse_elapsed;
+
+        for part in report.parts.iter() {
+            total += part.elapsed;
+
+            rows.push([
+                report.year.to_string(),
+                report.day.to_string(),
+                part.part.to_string(),
+                part.answer.clone().unwrap_or_else(|| String::from("-")),
+                part.answer.as_ref()
+                    .map_or_else(|| String::from("-"), |answer| answers.check(part.part, answer).to_string()),
+                format!("{:.2?}", report.parse_elapsed),
+                format!("{:.2?}", part.elapsed),
+            ]);
+        }
+    }
+
+    let mut widths = HEADERS.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    push_row(&mut table, &HEADERS.map(String::from), &widths);
+    push_row(&mut table, &widths.map(|width| "-".repeat(width)), &widths);
+    for row in rows.iter() {
+        push_row(&mut table, row, &widths);
+    }
+    table.push_str(&format!("Total: {:.2?}\n", total));
+
+    table
+}
+
+/// The answer and check columns are left aligned; everything else is right aligned.
+fn push_row(table: &mut String, row: &[String; 7], widths: &[usize; 7]) {
+    let cells = row.iter()
+        .zip(widths.iter())
+        .enumerate()
+        .map(|(column, (cell, &width))| {
+            if column == 3 || column == 4 {
+                format!("{:<width$}", cell)
+            } else {
+                format!("{:>width$}", cell)
+            }
+        })
+        .collect::<Vec<String>>();
+
+    table.push_str(cells.join("  ").trim_end());
+    table.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::{Part, PartReport};
+
+    use super::*;
+
+    #[test]
+    fn render_lists_each_part_and_total() {
+        let answers = Answers::parse("[part1]\nanswer = 35").unwrap();
+        let reports = vec![(Report {
+            year: 2023,
+            day: 5,
+            parse_elapsed: Duration::from_millis(1),
+            parts: vec![
+                PartReport { part: Part::One, answer: Some(String::from("35")), elapsed: Duration::from_millis(2) },
+                PartReport { part: Part::Two, answer: None, elapsed: Duration::from_millis(3) },
+            ],
+        }, answers)];
+
+        let table = render(&reports);
+
+        assert_eq!(
+            table,
+"Year  Day  Part  Answer  Check     Parse   Solve
+----  ---  ----  ------  -------  ------  ------
+2023    5     1  35      correct  1.00ms  2.00ms
+2023    5     2  -       -        1.00ms  3.00ms
+Total: 6.00ms
+");
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Part;
+
+/// What is known about the answers to one puzzle input: the accepted answer
+/// for each part, if any, and the guesses that were rejected.
+///
+/// Stored as TOML next to the input it belongs to, e.g.
+///
+/// ```toml
+/// [part1]
+/// answer = 6701
+///
+/// [part2]
+/// wrong = [314]
+/// too_high = [400]
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "PartAnswers::is_empty")]
+    pub part1: PartAnswers,
+    #[serde(default, skip_serializing_if = "PartAnswers::is_empty")]
+    pub part2: PartAnswers,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PartAnswers {
+    #[serde(
+        default,
+        deserialize_with = "optional_answer",
+        serialize_with = "serialize_optional_answer",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub answer: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "answer_list",
+        serialize_with = "serialize_answer_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub wrong: Vec<String>,
+    /// Rejected guesses the site said were too high.
+    #[serde(
+        default,
+        deserialize_with = "answer_list",
+        serialize_with = "serialize_answer_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub too_high: Vec<String>,
+    /// Rejected guesses the site said were too low.
+    #[serde(
+        default,
+        deserialize_with = "answer_list",
+        serialize_with = "serialize_answer_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub too_low: Vec<String>,
+}
+
+impl PartAnswers {
+    pub fn is_empty(&self) -> bool {
+        *self == PartAnswers::default()
+    }
+
+    /// The smallest guess known to be too high; every answer at or above it is wrong.
+    pub fn upper_bound(&self) -> Option<i128> {
+        self.too_high.iter().filter_map(|guess| guess.parse().ok()).min()
+    }
+
+    /// The largest guess known to be too low; every answer at or below it is wrong.
+    pub fn lower_bound(&self) -> Option<i128> {
+        self.too_low.iter().filter_map(|guess| guess.parse().ok()).max()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    /// Matches a guess that was already rejected.
+    KnownWrong,
+    /// At or above a guess that was rejected as too high.
+    TooHigh { bound: i128 },
+    /// At or below a guess that was rejected as too low.
+    TooLow { bound: i128 },
+    /// Nothing is known about this part yet.
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_wrong(&self) -> bool {
+        !matches!(self, Verdict::Correct | Verdict::Unknown)
+    }
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected } => write!(f, "WRONG (expected {})", expected),
+            Verdict::KnownWrong => write!(f, "WRONG (known wrong)"),
+            Verdict::TooHigh { bound } => write!(f, "WRONG (too high, >= {})", bound),
+            Verdict::TooLow { bound } => write!(f, "WRONG (too low, <= {})", bound),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers file at `path`. A missing file means nothing is
+    /// known yet, so it loads as empty rather than failing.
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(e),
+        };
+
+        Answers::parse(&text)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        std::fs::write(path, text)
+    }
+
+    pub fn part(&self, part: Part) -> &PartAnswers {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: Part) -> &mut PartAnswers {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Verdict {
+        let known = self.part(part);
+        let value = answer.parse::<i128>().ok();
+
+        match &known.answer {
+            Some(expected) if expected == answer => Verdict::Correct,
+            _ if known.wrong.iter().chain(known.too_high.iter()).chain(known.too_low.iter())
+                .any(|wrong| wrong == answer) => Verdict::KnownWrong,
+            _ if value.is_some_and(|value| known.upper_bound().is_some_and(|bound| value >= bound)) => {
+                Verdict::TooHigh { bound: known.upper_bound().unwrap() }
+            }
+            _ if value.is_some_and(|value| known.lower_bound().is_some_and(|bound| value <= bound)) => {
+                Verdict::TooLow { bound: known.lower_bound().unwrap() }
+            }
+            Some(expected) => Verdict::Wrong { expected: expected.clone() },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Remembers what the site said about `answer`.
+    pub fn record(&mut self, part: Part, answer: &str, feedback: Feedback) {
+        let known = self.part_mut(part);
+        let list = match feedback {
+            Feedback::Correct => {
+                known.answer = Some(answer.to_string());
+                return;
+            }
+            Feedback::Wrong => &mut known.wrong,
+            Feedback::TooHigh => &mut known.too_high,
+            Feedback::TooLow => &mut known.too_low,
+        };
+
+        if !list.iter().any(|guess| guess == answer) {
+            list.push(answer.to_string());
+        }
+    }
+}
+
+/// The answers file for an input file: `05.txt` is checked against `05.answers.toml`.
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    input_path.with_extension("answers.toml")
+}
+
+/// Answers are compared as text, but are usually written in the file as
+/// plain numbers, so accept either.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum AnswerValue {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<AnswerValue> for String {
+    fn from(value: AnswerValue) -> String {
+        match value {
+            AnswerValue::Integer(n) => n.to_string(),
+            AnswerValue::Text(s) => s,
+        }
+    }
+}
+
+fn optional_answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Option::<AnswerValue>::deserialize(deserializer).map(|value| value.map(String::from))
+}
+
+fn answer_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Vec::<AnswerValue>::deserialize(deserializer)
+        .map(|values| values.into_iter().map(String::from).collect())
+}
+
+/// Writes numeric answers back as numbers, so saved files look like hand-written ones.
+fn to_value(answer: &str) -> AnswerValue {
+    match answer.parse::<i64>() {
+        Ok(n) if n.to_string() == answer => AnswerValue::Integer(n),
+        _ => AnswerValue::Text(answer.to_string()),
+    }
+}
+
+fn serialize_optional_answer<S: Serializer>(answer: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    answer.as_deref().map(to_value).serialize(serializer)
+}
+
+fn serialize_answer_list<S: Serializer>(answers: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    answers.iter().map(|answer| to_value(answer)).collect::<Vec<_>>().serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "
+[part1]
+answer = 6701
+
+[part2]
+wrong = [314, \"abc\"]
+";
+
+    #[test]
+    fn parse_accepts_numbers_and_strings() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+
+        assert_eq!(answers.part1.answer, Some(String::from("6701")));
+        assert!(answers.part1.wrong.is_empty());
+        assert_eq!(answers.part2.answer, None);
+        assert_eq!(answers.part2.wrong, vec![String::from("314"), String::from("abc")]);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        assert!(Answers::parse("[part1]\nanswr = 1").is_err());
+    }
+
+    #[test]
+    fn check_reports_each_verdict() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+
+        assert_eq!(answers.check(Part::One, "6701"), Verdict::Correct);
+        assert_eq!(answers.check(Part::One, "13402"), Verdict::Wrong { expected: String::from("6701") });
+        assert_eq!(answers.check(Part::Two, "314"), Verdict::KnownWrong);
+        assert_eq!(answers.check(Part::Two, "303"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn check_rejects_answers_outside_known_bounds() {
+        let answers = Answers::parse("[part1]\ntoo_high = [500, 400]\ntoo_low = [100]").unwrap();
+
+        assert_eq!(answers.check(Part::One, "400"), Verdict::KnownWrong);
+        assert_eq!(answers.check(Part::One, "450"), Verdict::TooHigh { bound: 400 });
+        assert_eq!(answers.check(Part::One, "99"), Verdict::TooLow { bound: 100 });
+        assert_eq!(answers.check(Part::One, "250"), Verdict::Unknown);
+        assert_eq!(answers.check(Part::One, "abc"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn record_then_check_round_trips_through_toml() {
+        let mut answers = Answers::default();
+        answers.record(Part::Two, "314", Feedback::Wrong);
+        answers.record(Part::Two, "314", Feedback::Wrong);
+        answers.record(Part::Two, "900", Feedback::TooHigh);
+        answers.record(Part::Two, "303", Feedback::Correct);
+        answers.record(Part::One, "abc", Feedback::TooLow);
+        assert_eq!(toml::to_string(&Answers::default()).unwrap(), "");
+
+        let text = toml::to_string(&answers).unwrap();
+        assert_eq!(
+            text,
+"[part1]
+too_low = [\"abc\"]
+
+[part2]
+answer = 303
+wrong = [314]
+too_high = [900]
+");
+
+        let reloaded = Answers::parse(&text).unwrap();
+        assert_eq!(reloaded, answers);
+        assert_eq!(reloaded.check(Part::Two, "303"), Verdict::Correct);
+        assert_eq!(reloaded.check(Part::Two, "314"), Verdict::KnownWrong);
+    }
+
+    #[test]
+    fn load_treats_missing_file_as_empty() {
+        let answers = Answers::load(Path::new("does/not/exist.answers.toml")).unwrap();
+
+        assert_eq!(answers, Answers::default());
+    }
+
+    #[test]
+    fn answers_path_sits_next_to_input() {
+        assert_eq!(answers_path(Path::new("inputs/2023/05.txt")), PathBuf::from("inputs/2023/05.answers.toml"));
+        assert_eq!(answers_path(Path::new("part1.txt")), PathBuf::from("part1.answers.toml"));
+    }
+}
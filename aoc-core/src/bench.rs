@@ -0,0 +1,33 @@
+//! Criterion benchmarks shared by every day crate.
+//!
+//! Each day has a `benches/solution.rs` that hands its bundled input to
+//! [`bench_solution`]. Save a baseline with `cargo bench-save` before a
+//! change and compare against it with `cargo bench-check` afterwards.
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use crate::Solution;
+
+/// Benchmarks parsing, part one and part two of `S` separately, as the
+/// group `<year>/day-<day>`. Part two is skipped until it has been solved,
+/// and the whole day is skipped while its input is still blank, as a newly
+/// scaffolded day's sample is.
+pub fn bench_solution<S: Solution>(c: &mut Criterion, input: &str) {
+    let name = format!("{}/day-{:02}", S::YEAR, S::DAY);
+    if input.trim().is_empty() {
+        eprintln!("Skipping {}: there is no input to benchmark yet", name);
+        return;
+    }
+
+    let parsed = S::parse(input).expect("Could not parse puzzle input");
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+    group.bench_function("part 1", |b| b.iter(|| S::part_one(black_box(&parsed))));
+    if S::part_two(&parsed).is_some() {
+        group.bench_function("part 2", |b| b.iter(|| S::part_two(black_box(&parsed))));
+    }
+    group.finish();
+}
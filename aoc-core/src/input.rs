@@ -0,0 +1,136 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::answers::answers_path;
+
+/// Where puzzle inputs live when no other location is given.
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+/// Environment variable that overrides [`DEFAULT_INPUTS_DIR`].
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// A directory laid out as `<dir>/<year>/<day>.txt`, e.g. `inputs/2023/05.txt`.
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    /// `-` means stdin; anything else is a path to the input file.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The inputs directory named by `AOC_INPUTS_DIR`, or `inputs` if unset.
+    pub fn default_directory() -> InputSource {
+        InputSource::Directory(inputs_dir())
+    }
+
+    /// Reads the first command line argument as an input source, falling
+    /// back to the default inputs directory.
+    pub fn from_args() -> InputSource {
+        std::env::args()
+            .nth(1)
+            .map_or_else(InputSource::default_directory, |arg| InputSource::from_arg(&arg))
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => read_stdin(),
+            InputSource::Directory(dir) => read_file(&input_path(dir, year, day)),
+        }
+    }
+
+    /// Where the known answers for this input are kept. Input read from
+    /// stdin has no answers file.
+    pub fn answers_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::File(path) => Some(answers_path(path)),
+            InputSource::Stdin => None,
+            InputSource::Directory(dir) => Some(answers_path(&input_path(dir, year, day))),
+        }
+    }
+}
+
+pub fn inputs_dir() -> PathBuf {
+    std::env::var_os(INPUTS_DIR_VAR)
+        .map_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR), PathBuf::from)
+}
+
+pub fn input_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("{:02}.txt", day))
+}
+
+/// Reads the file `arg` names, or stdin if it is `-`, for tools whose input
+/// is not a day's puzzle input and so has no year or day.
+pub fn read_arg(arg: &str) -> io::Result<String> {
+    match InputSource::from_arg(arg) {
+        InputSource::File(path) => read_file(&path),
+        _ => read_stdin(),
+    }
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Like [`std::fs::read_to_string`], but the error names the file.
+fn read_file(path: &Path) -> io::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_arg_treats_dash_as_stdin() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(InputSource::from_arg("day5.txt"), InputSource::File(PathBuf::from("day5.txt")));
+    }
+
+    #[test]
+    fn read_arg_reads_the_named_file() {
+        let path = std::env::temp_dir().join(format!("aoc-core-read-arg-{}.txt", std::process::id()));
+        std::fs::write(&path, "rows\n1").unwrap();
+
+        let input = read_arg(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.unwrap(), "rows\n1");
+        assert!(read_arg("no-such-file.txt").unwrap_err().to_string().contains("no-such-file.txt"));
+    }
+
+    #[test]
+    fn input_path_pads_the_day() {
+        assert_eq!(input_path(Path::new("inputs"), 2023, 5), PathBuf::from("inputs/2023/05.txt"));
+        assert_eq!(input_path(Path::new("inputs"), 2023, 12), PathBuf::from("inputs/2023/12.txt"));
+    }
+
+    #[test]
+    fn read_from_directory_uses_year_and_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-core-input-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("2023")).unwrap();
+        std::fs::write(dir.join("2023").join("05.txt"), "seeds: 1 2").unwrap();
+
+        let input = InputSource::Directory(dir.clone()).read(2023, 5);
+        let missing = InputSource::Directory(dir.clone()).read(2023, 6);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(input.unwrap(), "seeds: 1 2");
+        let error = missing.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("06.txt"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Pieces shared by every Advent of Code day crate.
+//!
+//! Each day implements [`Solution`] so that the runner, benchmarks and tests
+//! can parse and solve any day the same way.
+
+pub mod answers;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod input;
+mod parse;
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+pub use crate::parse::{parse_number, ParseError};
+
+/// A single day's puzzle: how to parse its input and how to answer each part.
+///
+/// Parsing is separate from solving so that both parts can share one parsed
+/// input, and so that each step can be timed on its own.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// The parsed puzzle input. It may borrow from the raw input text.
+    type Parsed<'a>;
+
+    /// The answer type for both parts.
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Self::Answer;
+
+    /// Returns `None` until part two has been solved (and on days, like the
+    /// 25th, that only have one part).
+    fn part_two(_parsed: &Self::Parsed<'_>) -> Option<Self::Answer> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The outcome of running one part of a day, with how long it took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub part: Part,
+    /// `None` if the day has no solution for this part yet.
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+/// The outcome of parsing a day's input once and running the requested parts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+/// Object-safe view of a [`Solution`], so that days with different parsed
+/// and answer types can sit side by side in one list.
+pub trait Solver {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Report, ParseError>;
+}
+
+struct SolutionSolver<S>(PhantomData<S>);
+
+impl<S: Solution> Solver for SolutionSolver<S> {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Report, ParseError> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_elapsed = start.elapsed();
+
+        let parts = parts.iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => Some(S::part_one(&parsed)),
+                    Part::Two => S::part_two(&parsed),
+                };
+                let elapsed = start.elapsed();
+
+                PartReport { part, answer: answer.map(|a| a.to_string()), elapsed }
+            })
+            .collect();
+
+        Ok(Report { year: S::YEAR, day: S::DAY, parse_elapsed, parts })
+    }
+}
+
+pub fn solver<S: Solution + 'static>() -> Box<dyn Solver> {
+    Box::new(SolutionSolver::<S>(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Parsed<'a> = Vec<i32>;
+        type Answer = i32;
+
+        fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+            input.lines()
+                .enumerate()
+                .map(|(index, line)| parse_number(index + 1, line, line))
+                .collect()
+        }
+
+        fn part_one(numbers: &Vec<i32>) -> i32 {
+            numbers.iter().map(|n| n * 2).sum()
+        }
+    }
+
+    #[test]
+    fn solver_reports_each_requested_part() {
+        let report = solver::<Doubler>().run("1\n2\n3", &Part::BOTH).unwrap();
+
+        assert_eq!((report.year, report.day), (2000, 1));
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].part, Part::One);
+        assert_eq!(report.parts[0].answer, Some(String::from("12")));
+        assert_eq!(report.parts[1].part, Part::Two);
+        assert_eq!(report.parts[1].answer, None);
+    }
+
+    #[test]
+    fn solver_only_runs_requested_parts() {
+        let report = solver::<Doubler>().run("1", &[Part::One]).unwrap();
+
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].answer, Some(String::from("2")));
+    }
+
+    #[test]
+    fn solver_returns_parse_errors() {
+        let error = solver::<Doubler>().run("1\nx", &Part::BOTH).unwrap_err();
+
+        assert_eq!(error.to_string(), "line 2, column 1: expected a number: `x`");
+    }
+}
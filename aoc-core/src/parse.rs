@@ -0,0 +1,106 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Why a puzzle input could not be parsed, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// 1-based column (in bytes) where the offending text starts.
+    pub column: usize,
+    /// The text that could not be parsed; empty if something is missing.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error about `part`, which must be a slice of `line`, the
+    /// `line_number`th line of the input.
+    pub fn at(line_number: usize, line: &str, part: &str, message: &str) -> ParseError {
+        ParseError {
+            line: line_number,
+            column: column_of(line, part),
+            text: part.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// An error about a whole line.
+    pub fn line(line_number: usize, line: &str, message: &str) -> ParseError {
+        ParseError::at(line_number, line, line, message)
+    }
+
+    /// An error about something missing after the last line of `input`.
+    pub fn end_of_input(input: &str, message: &str) -> ParseError {
+        ParseError {
+            line: input.lines().count() + 1,
+            column: 1,
+            text: String::new(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+
+        if !self.text.is_empty() {
+            write!(f, ": `{}`", self.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `part`, a slice of `line`, as a number.
+pub fn parse_number<T: FromStr>(line_number: usize, line: &str, part: &str) -> Result<T, ParseError> {
+    part.parse::<T>()
+        .map_err(|_| ParseError::at(line_number, line, part, "expected a number"))
+}
+
+/// The 1-based column at which `part` starts within `line`. Falls back to 1
+/// if `part` clearly is not a slice of `line`.
+fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+
+    if offset <= line.len() {
+        offset + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_finds_column_of_part() {
+        let line = "50 98 x2";
+        let part = line.split(' ').nth(2).unwrap();
+
+        let error = ParseError::at(3, line, part, "expected a number");
+
+        assert_eq!(error, ParseError { line: 3, column: 7, text: String::from("x2"), message: String::from("expected a number") });
+        assert_eq!(error.to_string(), "line 3, column 7: expected a number: `x2`");
+    }
+
+    #[test]
+    fn end_of_input_points_past_last_line() {
+        let error = ParseError::end_of_input("a\nb\n", "expected a map");
+
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.to_string(), "line 3, column 1: expected a map");
+    }
+
+    #[test]
+    fn parse_number_reports_offending_text() {
+        let line = "1 two 3";
+
+        assert_eq!(parse_number::<i64>(1, line, &line[0..1]), Ok(1));
+        assert_eq!(parse_number::<i64>(1, line, &line[2..5]).unwrap_err().column, 3);
+    }
+}